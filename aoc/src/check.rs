@@ -0,0 +1,200 @@
+use crate::days;
+use aoc_common::{Answer, Answers, ErrorKind, InputArgs, InputKind, Part, PartResult, RunArgs};
+
+/// how a solved part compares to its recorded answer
+#[derive(Clone)]
+enum Status {
+    Pass,
+    Fail,
+    Error(String),
+    /// the day has not solved the part yet
+    NotImplemented,
+    /// there is no recorded answer to compare against
+    Unrecorded,
+    /// the input file could not be read
+    NoInput(String),
+}
+
+impl Status {
+    fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail | Status::Error(_))
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Status::Pass => "pass".to_owned(),
+            Status::Fail => "FAIL".to_owned(),
+            Status::Error(err) => format!("ERROR: {}", err),
+            Status::NotImplemented => "not implemented".to_owned(),
+            Status::Unrecorded => "unrecorded".to_owned(),
+            Status::NoInput(err) => format!("skipped: {}", err),
+        }
+    }
+}
+
+struct Row {
+    day: u8,
+    part: Part,
+    kind: InputKind,
+    expected: Option<Answer>,
+    actual: Option<Answer>,
+    status: Status,
+}
+
+/// Solves the example and real input (or only the one selected) of every
+/// selected day like `aoc run`, compares the answers with the recorded ones
+/// and prints a pass/fail table.
+///
+/// Returns whether no part failed or mismatched.
+pub fn check(selected: &[(u8, InputArgs)], args: &RunArgs, answers: &Answers) -> bool {
+    let mut rows = vec![];
+    for (day, paths) in selected {
+        let solver = days::solver(*day).expect("selected days are registered");
+        let results = aoc_common::run_solver(solver, paths, args);
+        rows.extend(results.into_iter().map(|result| row(result, answers)));
+    }
+
+    print_table(&rows);
+    !rows.iter().any(|row| row.status.is_failure())
+}
+
+fn row(result: PartResult, answers: &Answers) -> Row {
+    let expected = answers
+        .expected(result.day, result.part, result.kind)
+        .cloned();
+    let (actual, status) = match result.answer {
+        Err(err) if err.is_not_implemented() => (None, Status::NotImplemented),
+        Err(err) if matches!(err.kind, ErrorKind::Io(_)) => {
+            (None, Status::NoInput(err.to_string()))
+        }
+        Err(err) => (None, Status::Error(err.to_string())),
+        Ok(actual) => {
+            let status = match &expected {
+                None => Status::Unrecorded,
+                Some(expected) if *expected == actual => Status::Pass,
+                Some(_) => Status::Fail,
+            };
+            (Some(actual), status)
+        }
+    };
+    Row {
+        day: result.day,
+        part: result.part,
+        kind: result.kind,
+        expected,
+        actual,
+        status,
+    }
+}
+
+/// answers are shown on a single line, multi-line ones with escaped newlines
+fn cell(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(answer) => answer.to_string().replace('\n', "\\n"),
+        None => "-".to_owned(),
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let header = ["day", "part", "input", "expected", "actual", "status"].map(str::to_owned);
+    let lines: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            [
+                format!("{:02}", row.day),
+                row.part.to_string(),
+                row.kind.to_string(),
+                cell(&row.expected),
+                cell(&row.actual),
+                row.status.label(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|column| column.len());
+    for line in &lines {
+        for (width, column) in widths.iter_mut().zip(line) {
+            *width = (*width).max(column.len());
+        }
+    }
+    for line in std::iter::once(&header).chain(&lines) {
+        let columns: Vec<String> = line
+            .iter()
+            .zip(widths)
+            .map(|(column, width)| format!("{:width$}", column, width = width))
+            .collect();
+        println!("{}", columns.join("  ").trim_end());
+    }
+
+    let passed = rows
+        .iter()
+        .filter(|row| matches!(row.status, Status::Pass))
+        .count();
+    let failed = rows.iter().filter(|row| row.status.is_failure()).count();
+    println!(
+        "{} passed, {} failed, {} not checked",
+        passed,
+        failed,
+        rows.len() - passed - failed
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Error;
+    use std::io;
+
+    /// the row of day 1's `part` on `kind` with `answer`, against the
+    /// example's recorded answers
+    fn check_row(part: Part, kind: InputKind, answer: aoc_common::Result<Answer>) -> Row {
+        let answers = Answers::parse("[day_01.example]\npt1 = 11\npt2 = 31\n").unwrap();
+        let result = PartResult {
+            day: 1,
+            part,
+            kind,
+            answer,
+            duration: None,
+        };
+        row(result, &answers)
+    }
+
+    #[test]
+    fn compares_answers_with_the_recorded_ones() {
+        let pass = check_row(Part::One, InputKind::Example, Ok(Answer::Int(11)));
+        assert_eq!(pass.status.label(), "pass");
+        assert_eq!(
+            (pass.expected, pass.actual),
+            (Some(Answer::Int(11)), Some(Answer::Int(11)))
+        );
+        assert!(!pass.status.is_failure());
+
+        let fail = check_row(Part::Two, InputKind::Example, Ok(Answer::Int(30)));
+        assert_eq!(fail.status.label(), "FAIL");
+        assert!(fail.status.is_failure());
+
+        let unrecorded = check_row(Part::One, InputKind::Real, Ok(Answer::Int(11)));
+        assert_eq!(unrecorded.status.label(), "unrecorded");
+        assert_eq!(unrecorded.expected, None);
+        assert!(!unrecorded.status.is_failure());
+    }
+
+    #[test]
+    fn classifies_errors() {
+        let error = check_row(Part::One, InputKind::Example, Err(Error::solve("no pairs")));
+        assert_eq!(error.status.label(), "ERROR: no pairs");
+        assert_eq!(error.actual, None);
+        assert!(error.status.is_failure());
+
+        let not_implemented = Err(Error::not_implemented());
+        let not_implemented = check_row(Part::Two, InputKind::Example, not_implemented);
+        assert_eq!(not_implemented.status.label(), "not implemented");
+        assert!(!not_implemented.status.is_failure());
+
+        let missing = io::Error::new(io::ErrorKind::NotFound, "not found");
+        let no_input = Err(Error::from(missing).with_file("input.txt"));
+        let no_input = check_row(Part::One, InputKind::Real, no_input);
+        assert_eq!(no_input.status.label(), "skipped: input.txt: not found");
+        assert!(!no_input.status.is_failure());
+    }
+}
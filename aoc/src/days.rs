@@ -0,0 +1,38 @@
+use aoc_common::{DaySolver, InputArgs, Solution};
+use std::path::Path;
+
+/// Registers every day's [`Solution`] with the runner.
+macro_rules! days {
+    ($($solution:ty),* $(,)?) => {
+        /// all registered days, in calendar order
+        pub const DAYS: &[u8] = &[$(<$solution as Solution>::DAY),*];
+
+        /// the solver of a day, or `None` if the day is not registered
+        pub fn solver(day: u8) -> Option<DaySolver> {
+            $(
+                if day == <$solution as Solution>::DAY {
+                    return Some(DaySolver::of::<$solution>());
+                }
+            )*
+            None
+        }
+    };
+}
+
+days! {
+    day_01::Day01,
+    day_02::Day02,
+    day_03::Day03,
+    day_04::Day04,
+    day_05::Day05,
+    day_06::Day06,
+}
+
+/// example and input paths of a day inside the inputs directory
+pub fn day_paths(inputs_dir: &Path, day: u8) -> InputArgs {
+    let day_dir = inputs_dir.join(format!("day_{:02}", day));
+    InputArgs {
+        example_path: day_dir.join("example.txt"),
+        input_path: day_dir.join("input.txt"),
+    }
+}
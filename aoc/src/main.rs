@@ -0,0 +1,150 @@
+use aoc_common::{Answers, InputArgs, RunArgs};
+use clap::{Parser as ArgParser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod check;
+mod days;
+mod new;
+
+#[derive(ArgParser)]
+#[command(author, version, about)]
+/// run the advent of code solutions
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// run a single day, or all of them
+    Run {
+        /// day number, or `all`
+        #[arg(value_parser = parse_day_selection)]
+        day: DaySelection,
+
+        #[command(flatten)]
+        run: RunArgs,
+
+        /// directory containing the `day_NN` input directories
+        #[arg(long, env = "INPUTS_DIR", default_value = ".")]
+        inputs_dir: PathBuf,
+
+        /// example file of a single day instead of the one in the inputs directory, `-` reads stdin
+        #[arg(long)]
+        example: Option<PathBuf>,
+
+        /// input file of a single day instead of the one in the inputs directory, `-` reads stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// compare the answers against the answers file instead of printing them
+        #[arg(long)]
+        check: bool,
+
+        /// answers file used by `--check`
+        #[arg(long, env = "ANSWERS_FILE", default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// create a new day crate from the template and register it
+    New {
+        /// day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// root of the workspace
+        #[arg(long, default_value = ".")]
+        workspace_dir: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DaySelection {
+    All,
+    Day(u8),
+}
+
+fn parse_day_selection(day: &str) -> Result<DaySelection, String> {
+    if day == "all" {
+        return Ok(DaySelection::All);
+    }
+    let day: u8 = day
+        .parse()
+        .map_err(|_| format!("expected a day number or `all`, got `{}`", day))?;
+    if days::DAYS.contains(&day) {
+        Ok(DaySelection::Day(day))
+    } else {
+        Err(format!(
+            "day {} is not implemented, available: {:?}",
+            day,
+            days::DAYS
+        ))
+    }
+}
+
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    match args.command {
+        Command::Run {
+            day,
+            run,
+            inputs_dir,
+            example,
+            input,
+            check,
+            answers,
+        } => {
+            let selected: Vec<(u8, InputArgs)> = match day {
+                DaySelection::All if example.is_some() || input.is_some() => {
+                    return Err("--example and --input can only be used for a single day".into())
+                }
+                DaySelection::All => days::DAYS
+                    .iter()
+                    .map(|&day| (day, days::day_paths(&inputs_dir, day)))
+                    .collect(),
+                DaySelection::Day(day) => {
+                    let paths = days::day_paths(&inputs_dir, day);
+                    let paths = InputArgs {
+                        example_path: example.unwrap_or(paths.example_path),
+                        input_path: input.unwrap_or(paths.input_path),
+                    };
+                    vec![(day, paths)]
+                }
+            };
+            if check {
+                let answers = Answers::load(&answers)?;
+                if !check::check(&selected, &run, &answers) {
+                    return Ok(ExitCode::FAILURE);
+                }
+                return Ok(ExitCode::SUCCESS);
+            }
+            if let Some(runs) = run.bench {
+                let mut bench_results = vec![];
+                let mut failed = false;
+                for (day, paths) in selected {
+                    let solver = days::solver(day).expect("selected days are registered");
+                    match aoc_common::bench_solver(solver, &paths, &run, runs) {
+                        Ok(results) => bench_results.extend(results),
+                        Err(err) => {
+                            eprintln!("day {:02} failed: {}", day, err);
+                            failed = true;
+                        }
+                    }
+                }
+                aoc_common::print_report(&bench_results, run.bench_format)?;
+                if failed {
+                    return Ok(ExitCode::FAILURE);
+                }
+            } else {
+                let mut results = vec![];
+                for (day, paths) in selected {
+                    let solver = days::solver(day).expect("selected days are registered");
+                    results.extend(aoc_common::run_solver(solver, &paths, &run));
+                }
+                return aoc_common::print_results(&results, run.format);
+            }
+        }
+        Command::New { day, workspace_dir } => new::new_day(&workspace_dir, day)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,248 @@
+use std::error::Error;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::Path;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+
+/// Creates a `day_NN` crate from the templates and registers it with the
+/// workspace, the `aoc` runner and the answers file.
+///
+/// Refuses to touch a day that already exists.
+pub fn new_day(workspace_dir: &Path, day: u8) -> Result<(), Box<dyn Error>> {
+    let name = format!("day_{:02}", day);
+    let day_dir = workspace_dir.join(&name);
+    if day_dir.exists() {
+        return Err(format!(
+            "{} already exists, refusing to overwrite it",
+            day_dir.display()
+        )
+        .into());
+    }
+
+    // prepare every registration before writing anything, so a failure
+    // does not leave a half registered day behind
+    let workspace_toml_path = workspace_dir.join("Cargo.toml");
+    let runner_toml_path = workspace_dir.join("aoc").join("Cargo.toml");
+    let days_rs_path = workspace_dir.join("aoc").join("src").join("days.rs");
+    let answers_path = workspace_dir.join("answers.toml");
+    let workspace_toml = register_member(&read(&workspace_toml_path)?, &name)?;
+    let runner_toml = register_dependency(&read(&runner_toml_path)?, &name)?;
+    let days_rs = register_solution(&read(&days_rs_path)?, day)?;
+    let answers = format!(
+        "{}\n\n[{}.example]\n",
+        read(&answers_path)?.trim_end(),
+        name
+    );
+
+    create_dir_all(day_dir.join("src"))?;
+    write(day_dir.join("Cargo.toml"), fill(CARGO_TOML, day))?;
+    write(day_dir.join("src").join("main.rs"), fill(MAIN_RS, day))?;
+    write(day_dir.join("src").join("lib.rs"), fill(LIB_RS, day))?;
+    write(day_dir.join("example.txt"), "")?;
+
+    write(workspace_toml_path, workspace_toml)?;
+    write(runner_toml_path, runner_toml)?;
+    write(days_rs_path, days_rs)?;
+    write(answers_path, answers)?;
+
+    println!(
+        "created {}, paste the example into {}",
+        name,
+        day_dir.join("example.txt").display()
+    );
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err).into())
+}
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("{{day}}", &format!("{:02}", day))
+        .replace("{{day_number}}", &day.to_string())
+}
+
+/// Inserts `line` into the block between the first line matching `start`
+/// and the next line matching `end`, before the first line of the block
+/// that sorts after it, so blocks kept in order stay that way.
+fn insert_sorted(text: &str, start: &str, end: &str, line: &str) -> Result<String, Box<dyn Error>> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let start_at = lines
+        .iter()
+        .position(|l| l.trim_end() == start)
+        .ok_or_else(|| format!("could not find `{}`", start))?;
+    let end_at = start_at
+        + lines[start_at..]
+            .iter()
+            .position(|l| l.trim_end() == end)
+            .ok_or_else(|| format!("could not find the `{}` closing `{}`", end, start))?;
+    let insert_at = (start_at + 1..end_at)
+        .find(|&i| lines[i].trim() > line.trim())
+        .unwrap_or(end_at);
+    lines.insert(insert_at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// adds the day to the `members` of the workspace manifest
+fn register_member(workspace_toml: &str, name: &str) -> Result<String, Box<dyn Error>> {
+    insert_sorted(
+        workspace_toml,
+        "members = [",
+        "]",
+        &format!("    \"{}\",", name),
+    )
+    .map_err(|err| format!("workspace Cargo.toml: {}", err).into())
+}
+
+/// adds the day as a dependency of the runner, among the other days in
+/// order
+fn register_dependency(runner_toml: &str, name: &str) -> Result<String, Box<dyn Error>> {
+    let mut lines: Vec<&str> = runner_toml.lines().collect();
+    let dependency = format!("{} = {{ path = \"../{}\" }}", name, name);
+    let later_day = lines
+        .iter()
+        .position(|line| line.starts_with("day_") && *line > dependency.as_str());
+    let after_days = || {
+        lines
+            .iter()
+            .rposition(|line| line.starts_with("day_") || line.trim_end() == "[dependencies]")
+            .map(|at| at + 1)
+    };
+    let insert_at = later_day
+        .or_else(after_days)
+        .ok_or("aoc/Cargo.toml: could not find `[dependencies]`")?;
+    lines.insert(insert_at, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// adds the day's solution to the `days!` registry of the runner, which
+/// lists the days in calendar order
+fn register_solution(days_rs: &str, day: u8) -> Result<String, Box<dyn Error>> {
+    insert_sorted(
+        days_rs,
+        "days! {",
+        "}",
+        &format!("    day_{:02}::Day{:02},", day, day),
+    )
+    .map_err(|err| format!("aoc/src/days.rs: {}", err).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE_TOML: &str =
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_01\",\n    \"day_03\",\n]\n";
+    const RUNNER_TOML: &str = concat!(
+        "[dependencies]\n",
+        "clap = \"4.0\"\n",
+        "day_01 = { path = \"../day_01\" }\n",
+        "day_03 = { path = \"../day_03\" }\n",
+        "\n",
+        "[dev-dependencies]\n",
+    );
+    const DAYS_RS: &str =
+        "days! {\n    day_01::Day01,\n    day_03::Day03,\n}\n\nfn day_paths() {\n}\n";
+
+    #[test]
+    fn inserts_in_order_before_the_end() {
+        let text = "start\nb\nd\nend\nz\n";
+        assert_eq!(
+            insert_sorted(text, "start", "end", "c").unwrap(),
+            "start\nb\nc\nd\nend\nz\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "start", "end", "e").unwrap(),
+            "start\nb\nd\ne\nend\nz\n"
+        );
+    }
+
+    #[test]
+    fn insert_needs_both_markers() {
+        let err = insert_sorted("b\nend\n", "start", "end", "c").unwrap_err();
+        assert_eq!(err.to_string(), "could not find `start`");
+        let err = insert_sorted("start\nb\n", "start", "end", "c").unwrap_err();
+        assert_eq!(err.to_string(), "could not find the `end` closing `start`");
+    }
+
+    #[test]
+    fn registers_members_in_order() {
+        assert_eq!(
+            register_member(WORKSPACE_TOML, "day_02").unwrap(),
+            WORKSPACE_TOML.replace("\"day_03\"", "\"day_02\",\n    \"day_03\"")
+        );
+        assert_eq!(
+            register_member(WORKSPACE_TOML, "day_04").unwrap(),
+            WORKSPACE_TOML.replace("\"day_03\",", "\"day_03\",\n    \"day_04\",")
+        );
+        let err = register_member("[workspace]\n", "day_02").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "workspace Cargo.toml: could not find `members = [`"
+        );
+    }
+
+    #[test]
+    fn registers_dependencies_in_order() {
+        let day_02 = "day_02 = { path = \"../day_02\" }";
+        assert_eq!(
+            register_dependency(RUNNER_TOML, "day_02").unwrap(),
+            RUNNER_TOML.replace("day_03 =", &format!("{}\nday_03 =", day_02))
+        );
+        let day_04 = "day_04 = { path = \"../day_04\" }";
+        assert_eq!(
+            register_dependency(RUNNER_TOML, "day_04").unwrap(),
+            RUNNER_TOML.replace("\n\n[dev", &format!("\n{}\n\n[dev", day_04))
+        );
+        assert_eq!(
+            register_dependency("[dependencies]\nclap = \"4.0\"\n", "day_01").unwrap(),
+            "[dependencies]\nday_01 = { path = \"../day_01\" }\nclap = \"4.0\"\n"
+        );
+        let err = register_dependency("[package]\n", "day_01").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "aoc/Cargo.toml: could not find `[dependencies]`"
+        );
+    }
+
+    #[test]
+    fn registers_solutions_in_calendar_order() {
+        assert_eq!(
+            register_solution(DAYS_RS, 2).unwrap(),
+            DAYS_RS.replace("    day_03", "    day_02::Day02,\n    day_03")
+        );
+        assert_eq!(
+            register_solution(DAYS_RS, 12).unwrap(),
+            DAYS_RS.replace("Day03,\n", "Day03,\n    day_12::Day12,\n")
+        );
+        let err = register_solution("days! {\n", 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "aoc/src/days.rs: could not find the `}` closing `days! {`"
+        );
+    }
+
+    #[test]
+    fn refuses_to_overwrite_a_day() {
+        let workspace_dir = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        create_dir_all(workspace_dir.join("day_07")).unwrap();
+        let err = new_day(&workspace_dir, 7).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "{} already exists, refusing to overwrite it",
+                workspace_dir.join("day_07").display()
+            )
+        );
+        // nothing was registered or written next to it
+        let entries: Vec<_> = std::fs::read_dir(&workspace_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, ["day_07"]);
+        std::fs::remove_dir_all(&workspace_dir).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{Answer, InputKind, Part};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Recorded answers, read from a TOML file keyed by day, input kind and part:
+///
+/// ```toml
+/// [day_05.example]
+/// pt1 = 143
+/// pt2 = 123
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, BTreeMap<String, Answer>>>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, Box<dyn Error>> {
+        let answers_str = read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        Self::parse(&answers_str).map_err(|err| format!("{}: {}", path.display(), err).into())
+    }
+
+    pub fn parse(answers_str: &str) -> Result<Answers, toml::de::Error> {
+        toml::from_str(answers_str)
+    }
+
+    /// the recorded answer for a part of a day, if any
+    pub fn expected(&self, day: u8, part: Part, kind: InputKind) -> Option<&Answer> {
+        self.days
+            .get(&format!("day_{:02}", day))?
+            .get(kind.name())?
+            .get(&part.to_string())
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{BenchFormat, InputKind, Part, Result, Solution};
+use serde::Serialize;
+use std::error::Error;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// what was timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Pt1,
+    Pt2,
+}
+
+impl Stage {
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Pt1 => "pt1",
+            Stage::Pt2 => "pt2",
+        }
+    }
+}
+
+/// timings of one stage of a day over a number of runs
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub day: u8,
+    pub input: &'static str,
+    pub stage: Stage,
+    pub runs: usize,
+    pub input_bytes: usize,
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub max_ns: u128,
+    /// input bytes processed per second at the median time, in MB/s
+    pub mb_per_s: f64,
+}
+
+impl BenchResult {
+    fn new(
+        day: u8,
+        kind: InputKind,
+        stage: Stage,
+        input_bytes: usize,
+        mut samples: Vec<Duration>,
+    ) -> Self {
+        samples.sort();
+        let median = samples[samples.len() / 2];
+        BenchResult {
+            day,
+            input: kind.name(),
+            stage,
+            runs: samples.len(),
+            input_bytes,
+            min_ns: samples[0].as_nanos(),
+            median_ns: median.as_nanos(),
+            max_ns: samples[samples.len() - 1].as_nanos(),
+            mb_per_s: input_bytes as f64 / 1e6 / median.as_secs_f64().max(f64::MIN_POSITIVE),
+        }
+    }
+}
+
+/// A fixed-seed linear congruential generator for synthetic benchmark
+/// inputs, so every run times the same input.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { state: seed }
+    }
+
+    /// the next number in `0..modulo`
+    pub fn below(&mut self, modulo: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) % modulo
+    }
+}
+
+/// Type-erased [`bench`], so days can be dispatched at runtime.
+pub type BenchFn = fn(InputKind, &str, Option<Part>, u32) -> Result<Vec<BenchResult>>;
+
+/// Times parsing an input of a day, and the requested part (or both) on the
+/// parsed input, `runs` times each.
+/// Parts that are not implemented yet are left out.
+pub fn bench<S: Solution>(
+    kind: InputKind,
+    input_str: &str,
+    part: Option<Part>,
+    runs: u32,
+) -> Result<Vec<BenchResult>> {
+    bench_with::<S>(kind, input_str, part, runs, &S::Options::default())
+}
+
+/// [`bench`] with the day's options.
+pub fn bench_with<S: Solution>(
+    kind: InputKind,
+    input_str: &str,
+    part: Option<Part>,
+    runs: u32,
+    options: &S::Options,
+) -> Result<Vec<BenchResult>> {
+    let mut samples = vec![];
+    let mut input = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed =
+            black_box(S::parse_input(black_box(input_str)).map_err(|err| err.with_day(S::DAY))?);
+        samples.push(start.elapsed());
+        input = Some(parsed);
+    }
+    let input = input.expect("runs is at least 1");
+    let mut results = vec![BenchResult::new(
+        S::DAY,
+        kind,
+        Stage::Parse,
+        input_str.len(),
+        samples,
+    )];
+
+    for part in Part::selected(part) {
+        let stage = match part {
+            Part::One => Stage::Pt1,
+            Part::Two => Stage::Pt2,
+        };
+        let solve = |input: &S::Input| match part {
+            Part::One => S::pt1(input, options),
+            Part::Two => S::pt2(input, options),
+        };
+        let mut samples = vec![];
+        for _ in 0..runs {
+            let start = Instant::now();
+            match black_box(solve(black_box(&input))) {
+                Ok(answer) => black_box(answer),
+                // an unsolved part of a scaffolded day has nothing to time
+                Err(err) if err.is_not_implemented() => break,
+                Err(err) => return Err(err.with_day(S::DAY)),
+            };
+            samples.push(start.elapsed());
+        }
+        if samples.is_empty() {
+            continue;
+        }
+        results.push(BenchResult::new(
+            S::DAY,
+            kind,
+            stage,
+            input_str.len(),
+            samples,
+        ));
+    }
+    Ok(results)
+}
+
+/// Prints a benchmark report in the requested format.
+pub fn print_report(
+    results: &[BenchResult],
+    format: BenchFormat,
+) -> std::result::Result<(), Box<dyn Error>> {
+    match format {
+        BenchFormat::Table => {
+            println!(
+                "{:<4} {:<8} {:<6} {:>6} {:>12} {:>12} {:>12} {:>10}",
+                "day", "input", "stage", "runs", "min", "median", "max", "MB/s"
+            );
+            for result in results {
+                println!(
+                    "{:<4} {:<8} {:<6} {:>6} {:>12} {:>12} {:>12} {:>10.2}",
+                    format!("{:02}", result.day),
+                    result.input,
+                    result.stage.name(),
+                    result.runs,
+                    format!("{:.2?}", Duration::from_nanos(result.min_ns as u64)),
+                    format!("{:.2?}", Duration::from_nanos(result.median_ns as u64)),
+                    format!("{:.2?}", Duration::from_nanos(result.max_ns as u64)),
+                    result.mb_per_s
+                );
+            }
+        }
+        BenchFormat::Csv => {
+            println!("day,input,stage,runs,input_bytes,min_ns,median_ns,max_ns,mb_per_s");
+            for result in results {
+                println!(
+                    "{},{},{},{},{},{},{},{},{:.3}",
+                    result.day,
+                    result.input,
+                    result.stage.name(),
+                    result.runs,
+                    result.input_bytes,
+                    result.min_ns,
+                    result.median_ns,
+                    result.max_ns,
+                    result.mb_per_s
+                );
+            }
+        }
+        BenchFormat::Json => println!("{}", serde_json::to_string_pretty(results)?),
+    }
+    Ok(())
+}
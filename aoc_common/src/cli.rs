@@ -0,0 +1,99 @@
+use crate::{InputKind, Part};
+use clap::{Args, ValueEnum};
+use std::fmt;
+use std::path::PathBuf;
+
+/// input file arguments shared by every day
+#[derive(Args, Debug, Clone)]
+pub struct InputArgs {
+    /// example file path, `-` reads stdin; skipped when it does not exist
+    #[arg(
+        long = "example",
+        alias = "example-path",
+        env = "EXAMPLE_FILE",
+        default_value = "example.txt"
+    )]
+    pub example_path: PathBuf,
+
+    /// input file path, `-` reads stdin
+    #[arg(
+        long = "input",
+        alias = "input-path",
+        env = "INPUT_FILE",
+        default_value = "input.txt"
+    )]
+    pub input_path: PathBuf,
+}
+
+/// arguments controlling how a day is run, shared by every day
+#[derive(Args, Debug, Clone)]
+pub struct RunArgs {
+    /// only run this part
+    #[arg(long)]
+    pub part: Option<Part>,
+
+    /// only run on this input
+    #[arg(long, value_enum)]
+    pub only: Option<InputKind>,
+
+    /// output format of the answers
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// time parsing and the parts over this many runs instead of printing answers
+    #[arg(long, value_name = "RUNS", value_parser = clap::value_parser!(u32).range(1..))]
+    pub bench: Option<u32>,
+
+    /// output format of the `--bench` report
+    #[arg(long, value_enum, default_value_t = BenchFormat::Table)]
+    pub bench_format: BenchFormat,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// how a day's `--explain` prints its rows
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExplainFormat {
+    #[default]
+    Text,
+    Csv,
+}
+
+/// integer type a day totals its answers in, wider ones for inputs whose
+/// totals do not fit in an [`i64`]
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Accumulator {
+    #[default]
+    I64,
+    I128,
+}
+
+impl Accumulator {
+    /// `total`, or `None` when it does not fit in the accumulator
+    pub fn fit(self, total: i128) -> Option<i128> {
+        match self {
+            Accumulator::I64 => i64::try_from(total).ok().map(i128::from),
+            Accumulator::I128 => Some(total),
+        }
+    }
+}
+
+impl fmt::Display for Accumulator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Accumulator::I64 => write!(f, "i64"),
+            Accumulator::I128 => write!(f, "i128"),
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchFormat {
+    Table,
+    Csv,
+    Json,
+}
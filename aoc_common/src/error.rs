@@ -0,0 +1,271 @@
+use crate::parse::ParseError;
+use std::fmt;
+use std::io;
+
+/// What went wrong.
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// an input could not be read
+    Io(io::Error),
+    /// an input does not match the format a day expects
+    Parse(ParseError),
+    /// an input parsed, but a part could not be solved for it
+    Solve(String),
+    /// a part of a scaffolded day that has not been solved yet
+    NotImplemented,
+}
+
+impl Clone for ErrorKind {
+    fn clone(&self) -> Self {
+        match self {
+            ErrorKind::Io(err) => ErrorKind::Io(io::Error::new(err.kind(), err.to_string())),
+            ErrorKind::Parse(err) => ErrorKind::Parse(err.clone()),
+            ErrorKind::Solve(message) => ErrorKind::Solve(message.clone()),
+            ErrorKind::NotImplemented => ErrorKind::NotImplemented,
+        }
+    }
+}
+
+/// Error of reading, parsing or solving a day's input, with whatever is
+/// known about where it happened.
+///
+/// Displays on a single line, e.g. `day 01, input.txt:2:3: unexpected 'x'`;
+/// the alternate form (`{:#}`) adds the offending line of the input with a
+/// caret under the column for parse errors.
+#[derive(Debug, Clone)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub day: Option<u8>,
+    /// input file the error happened in
+    pub file: Option<String>,
+    /// the line of the input a parse error points at
+    source_line: Option<String>,
+}
+
+/// Result of parsing an input or solving a part.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A part that could not be solved.
+    pub fn solve(message: impl Into<String>) -> Error {
+        ErrorKind::Solve(message.into()).into()
+    }
+
+    /// A part that has not been solved yet, which `aoc run` does not count as
+    /// failing.
+    pub fn not_implemented() -> Error {
+        ErrorKind::NotImplemented.into()
+    }
+
+    pub fn is_not_implemented(&self) -> bool {
+        matches!(self.kind, ErrorKind::NotImplemented)
+    }
+
+    pub fn with_day(mut self, day: u8) -> Error {
+        self.day.get_or_insert(day);
+        self
+    }
+
+    /// Attaches the input file the error happened in, and for parse errors
+    /// the offending line of its contents.
+    pub fn with_input(mut self, file: impl fmt::Display, input: &str) -> Error {
+        self.file.get_or_insert_with(|| file.to_string());
+        if let ErrorKind::Parse(err) = &self.kind {
+            let line = usize::try_from(err.line - 1).unwrap_or(0);
+            // positions are counted from after a byte order mark
+            self.source_line = input
+                .strip_prefix('\u{feff}')
+                .unwrap_or(input)
+                .lines()
+                .nth(line)
+                .map(|line| line.trim_end_matches('\r').to_owned());
+        }
+        self
+    }
+
+    /// Attaches the line a parse error is on, for inputs read line by line
+    /// where [`with_input`](Self::with_input) cannot be used.
+    pub fn with_source_line(mut self, line: &str) -> Error {
+        if let ErrorKind::Parse(_) = &self.kind {
+            self.source_line = Some(line.trim_end_matches(['\r', '\n']).to_owned());
+        }
+        self
+    }
+
+    /// Attaches the input file the error happened in, for errors where the
+    /// contents are not available.
+    pub fn with_file(mut self, file: impl fmt::Display) -> Error {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+
+    fn message(&self) -> String {
+        match &self.kind {
+            ErrorKind::Io(err) => err.to_string(),
+            ErrorKind::Parse(err) => err.message.clone(),
+            ErrorKind::Solve(message) => message.clone(),
+            ErrorKind::NotImplemented => "not implemented yet".to_owned(),
+        }
+    }
+
+    /// `day 01, input.txt:2:3`, or as much of it as is known
+    fn location(&self) -> Vec<String> {
+        let mut location = vec![];
+        if let Some(day) = self.day {
+            location.push(format!("day {:02}", day));
+        }
+        match (&self.file, &self.kind) {
+            (Some(file), ErrorKind::Parse(err)) => {
+                location.push(format!("{}:{}:{}", file, err.line, err.column))
+            }
+            (None, ErrorKind::Parse(err)) => {
+                location.push(format!("line {}, column {}", err.line, err.column))
+            }
+            (Some(file), _) => location.push(file.clone()),
+            (None, _) => {}
+        }
+        location
+    }
+
+    /// the offending line with a caret under the column of a parse error
+    fn excerpt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (ErrorKind::Parse(err), Some(source_line)) = (&self.kind, &self.source_line) else {
+            return Ok(());
+        };
+        let gutter = " ".repeat(err.line.to_string().len());
+        // keep tabs so the caret lines up with the text above it
+        let indent: String = source_line
+            .chars()
+            .take(usize::try_from(err.column - 1).unwrap_or(0))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(
+            f,
+            "\n{} |\n{} | {}\n{} | {}^",
+            gutter, err.line, source_line, gutter, indent
+        )
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = self.location();
+        if location.is_empty() {
+            write!(f, "{}", self.message())?;
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.message())?;
+        }
+        if f.alternate() {
+            self.excerpt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            ErrorKind::Parse(err) => Some(err),
+            ErrorKind::Solve(_) | ErrorKind::NotImplemented => None,
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            day: None,
+            file: None,
+            source_line: None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        ErrorKind::Io(err).into()
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        ErrorKind::Parse(err).into()
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::solve(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::solve(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(line: i32, column: i32) -> Error {
+        ParseError {
+            line,
+            column,
+            message: "unexpected `x`".to_owned(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn excerpt_points_at_the_column() {
+        let err = parse_error(2, 3).with_input("input.txt", "1 2\n3 x\n");
+        assert_eq!(err.to_string(), "input.txt:2:3: unexpected `x`");
+        assert_eq!(
+            format!("{:#}", err.with_day(1)),
+            "day 01, input.txt:2:3: unexpected `x`\n  |\n2 | 3 x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn excerpt_without_a_file() {
+        let err = parse_error(1, 2).with_source_line("1x\r\n");
+        assert_eq!(
+            format!("{:#}", err),
+            "line 1, column 2: unexpected `x`\n  |\n1 | 1x\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn caret_keeps_tabs() {
+        let err = parse_error(1, 3).with_input("input.txt", "1\tx\n");
+        assert_eq!(
+            format!("{:#}", err),
+            "input.txt:1:3: unexpected `x`\n  |\n1 | 1\tx\n  |  \t^"
+        );
+    }
+
+    #[test]
+    fn excerpt_skips_a_bom() {
+        let err = parse_error(1, 1).with_input("input.txt", "\u{feff}x\r\n");
+        assert_eq!(
+            format!("{:#}", err),
+            "input.txt:1:1: unexpected `x`\n  |\n1 | x\n  | ^"
+        );
+    }
+
+    #[test]
+    fn no_excerpt_past_the_end() {
+        let err = parse_error(3, 1).with_input("input.txt", "1\n2\n");
+        assert_eq!(format!("{:#}", err), "input.txt:3:1: unexpected `x`");
+    }
+
+    #[test]
+    fn no_excerpt_for_other_errors() {
+        let err = Error::solve("overflow").with_input("input.txt", "1\n");
+        assert_eq!(format!("{:#}", err), "input.txt: overflow");
+    }
+}
@@ -0,0 +1,25 @@
+//! Shared plumbing for the advent of code days: the [`Solution`] trait every
+//! day implements, the runner that reads, parses and solves its input, and the
+//! recorded answers the results are checked against.
+
+mod answer;
+mod answers;
+mod bench;
+mod cli;
+mod error;
+mod output;
+pub mod parse;
+mod runner;
+mod solution;
+
+pub use answer::Answer;
+pub use answers::Answers;
+pub use bench::{bench, bench_with, print_report, BenchFn, BenchResult, SeededRng, Stage};
+pub use cli::{Accumulator, BenchFormat, ExplainFormat, InputArgs, OutputFormat, RunArgs};
+pub use error::{Error, ErrorKind, Result};
+pub use output::print_results;
+pub use runner::{
+    bench_solver, open_input, read_input, read_parsed, run, run_solver, run_with, selected_input,
+    solve, solve_with, DaySolver, InputKind, Part, PartResult, SolveFn,
+};
+pub use solution::Solution;
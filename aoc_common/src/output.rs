@@ -0,0 +1,75 @@
+use crate::{Answer, OutputFormat, PartResult};
+use serde::Serialize;
+use std::error::Error;
+use std::process::ExitCode;
+
+/// a [`PartResult`] as it appears in the JSON document
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    day: u8,
+    part: u8,
+    input: &'static str,
+    answer: Option<&'a Answer>,
+    duration_ns: Option<u128>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    results: Vec<JsonResult<'a>>,
+}
+
+/// Prints the results of a run, either as lines of text or as a single JSON
+/// document, returning [`ExitCode::FAILURE`] when any of them failed. Parts
+/// that are not implemented yet are printed but do not fail the run.
+pub fn print_results(
+    results: &[PartResult],
+    format: OutputFormat,
+) -> Result<ExitCode, Box<dyn Error>> {
+    match format {
+        OutputFormat::Text => {
+            for result in results {
+                match &result.answer {
+                    // grids start on their own line so their rows line up
+                    Ok(answer @ Answer::Grid(_)) => println!(
+                        "day {:02} {} {}:\n{}",
+                        result.day, result.part, result.kind, answer
+                    ),
+                    Ok(answer) => println!(
+                        "day {:02} {} {}: {}",
+                        result.day, result.part, result.kind, answer
+                    ),
+                    Err(err) if err.is_not_implemented() => {
+                        eprintln!("{} {} skipped: {}", result.part, result.kind, err)
+                    }
+                    Err(err) => eprintln!("{} {} failed: {:#}", result.part, result.kind, err),
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let document = JsonDocument {
+                results: results
+                    .iter()
+                    .map(|result| JsonResult {
+                        day: result.day,
+                        part: result.part.number(),
+                        input: result.kind.name(),
+                        answer: result.answer.as_ref().ok(),
+                        duration_ns: result.duration.map(|duration| duration.as_nanos()),
+                        error: result.answer.as_ref().err().map(ToString::to_string),
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+    }
+    let failed = |result: &PartResult| match &result.answer {
+        Ok(_) => false,
+        Err(err) => !err.is_not_implemented(),
+    };
+    if results.iter().any(failed) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
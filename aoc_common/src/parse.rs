@@ -0,0 +1,631 @@
+//! Reusable combine parsers for puzzle inputs, and [`parse_all`] to run one
+//! over a whole input with line and column information in its errors.
+
+use crate::Error;
+use combine::error::{Commit, ParseError as _, StreamError};
+use combine::parser::char::{char, crlf, newline};
+use combine::parser::function::parser;
+use combine::parser::repeat::{sep_by1, sep_end_by1, skip_many, skip_many1};
+use combine::stream::position::{self, SourcePosition};
+use combine::stream::{easy, Stream, StreamErrorFor};
+use combine::{attempt, eof, look_ahead, many, none_of, one_of, optional, Parser};
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// Stream type the parsers are run on by [`parse_all`].
+pub type PositionStream<'a> = easy::Stream<position::Stream<&'a str, SourcePosition>>;
+
+/// A failed parse, with the 1-based line and column where it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: i32,
+    pub column: i32,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at a byte offset of `input`, for inputs that are not parsed
+    /// with combine.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> ParseError {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        ParseError {
+            line: before.matches('\n').count() as i32 + 1,
+            column: before[line_start..].chars().count() as i32 + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// combine's multi-line error listing
+struct EasyErrors<'a>(&'a [easy::Error<String, String>]);
+
+impl fmt::Display for EasyErrors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        easy::Error::fmt_errors(self.0, f)
+    }
+}
+
+impl From<easy::Errors<char, &str, SourcePosition>> for ParseError {
+    fn from(err: easy::Errors<char, &str, SourcePosition>) -> Self {
+        // escaped, so a newline token shows as `\n` instead of breaking the
+        // message
+        let err = err
+            .map_range(str::to_owned)
+            .map_token(|token| token.escape_debug().to_string());
+        // "Unexpected `x`\nExpected digit\n" becomes "unexpected `x`, expected digit"
+        let message = EasyErrors(&err.errors)
+            .to_string()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut chars = line.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        ParseError {
+            line: err.position.line,
+            column: err.position.column,
+            message,
+        }
+    }
+}
+
+/// Runs `parser` over the whole of `input`, failing if anything but blank
+/// lines is left.
+///
+/// A leading UTF-8 byte order mark is skipped, positions in errors are
+/// counted from after it.
+pub fn parse_all<'a, P>(parser: P, input: &'a str) -> Result<P::Output, ParseError>
+where
+    P: Parser<PositionStream<'a>>,
+{
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    parser
+        .skip(skip_many(one_of(" \t\r\n".chars()).expected("blank line")))
+        .skip(eof())
+        .parse(easy::Stream(position::Stream::new(input)))
+        .map(|(output, _rest)| output)
+        .map_err(ParseError::from)
+}
+
+/// An input read one line at a time, for inputs too large for
+/// [`parse_all`].
+///
+/// Accepts what `parse_all` does around the lines: a leading byte order mark,
+/// `\n` or `\r\n` line endings, and blank lines, which are skipped.
+pub struct LineReader<R> {
+    reader: R,
+    buffer: String,
+    number: usize,
+    blank_line: Option<usize>,
+}
+
+/// a line of a [`LineReader`] that is not blank
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    /// the line without its line ending
+    pub text: &'a str,
+    /// the first blank line before this one, as inputs usually only allow
+    /// blank lines at the end
+    pub after_blank: Option<usize>,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            buffer: String::new(),
+            number: 0,
+            blank_line: None,
+        }
+    }
+
+    /// The next line that is not blank, `None` at the end of the input.
+    pub fn next_line(&mut self) -> io::Result<Option<Line<'_>>> {
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+            self.number += 1;
+            let end = self.buffer.trim_end_matches(['\r', '\n']).len();
+            let start = match self.number {
+                1 if self.buffer.starts_with('\u{feff}') => '\u{feff}'.len_utf8(),
+                _ => 0,
+            };
+            if self.buffer[start..end].trim_matches([' ', '\t']).is_empty() {
+                self.blank_line.get_or_insert(self.number);
+                continue;
+            }
+            return Ok(Some(Line {
+                number: self.number,
+                text: &self.buffer[start..end],
+                after_blank: self.blank_line,
+            }));
+        }
+    }
+}
+
+impl<'a> Line<'a> {
+    /// Runs `parser` over the line like [`parse_all`], with errors on this
+    /// line of the input.
+    pub fn parse<P>(&self, parser: P) -> Result<P::Output, Error>
+    where
+        P: Parser<PositionStream<'a>>,
+    {
+        parse_all(parser, self.text).map_err(|err| self.error(err))
+    }
+
+    /// An error in the text of the line moved to its line of the input, with
+    /// the line attached for the caret excerpt.
+    pub fn error(&self, err: ParseError) -> Error {
+        let line = i32::try_from(self.number).unwrap_or(i32::MAX);
+        Error::from(ParseError { line, ..err }).with_source_line(self.text)
+    }
+}
+
+/// Integer types the integer parsers can accumulate into.
+pub trait Integer: Sized {
+    fn zero() -> Self;
+
+    /// `self * 10 + digit`, or `self * 10 - digit` when accumulating a
+    /// negative number, `None` when the result does not fit
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($int:ty),*) => {
+        $(
+            impl Integer for $int {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    let digit = Self::try_from(digit).ok()?;
+                    if negative {
+                        shifted.checked_sub(digit)
+                    } else {
+                        shifted.checked_add(digit)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Digits accumulated into `T`, failing at the first digit that makes the
+/// number overflow.
+///
+/// Without any digits it only expects a digit `after_sign`, otherwise the
+/// caller labels what it expected.
+fn digits<Input, T>(negative: bool, after_sign: bool) -> impl Parser<Input, Output = T>
+where
+    Input: Stream<Token = char>,
+    T: Integer,
+{
+    parser(move |input: &mut Input| {
+        let start = input.position();
+        let mut value = T::zero();
+        let mut consumed = false;
+        loop {
+            let checkpoint = input.checkpoint();
+            let position = input.position();
+            let digit = match input.uncons() {
+                Ok(c) => c.to_digit(10),
+                Err(_) => None,
+            };
+            let Some(digit) = digit else {
+                input
+                    .reset(checkpoint)
+                    .map_err(|err| Commit::Commit(err.into()))?;
+                break;
+            };
+            consumed = true;
+            value = match value.push_digit(digit as u8, negative) {
+                Some(value) => value,
+                None => {
+                    let err = StreamErrorFor::<Input>::message_format(format_args!(
+                        "integer does not fit in {}",
+                        std::any::type_name::<T>()
+                    ));
+                    return Err(Commit::Commit(
+                        Input::Error::from_error(position, err).into(),
+                    ));
+                }
+            };
+        }
+        if consumed {
+            Ok((value, Commit::Commit(())))
+        } else if after_sign {
+            let err = StreamErrorFor::<Input>::expected_static_message("digit");
+            Err(Commit::Peek(Input::Error::from_error(start, err).into()))
+        } else {
+            Err(Commit::Peek(Input::Error::empty(start).into()))
+        }
+    })
+}
+
+/// An unsigned integer accumulated into `T`, e.g. `42`, see
+/// [`integer_of`].
+pub fn unsigned_of<T, Input>() -> impl Parser<Input, Output = T>
+where
+    Input: Stream<Token = char>,
+    T: Integer,
+{
+    digits(false, false).expected("unsigned integer")
+}
+
+/// An integer with an optional sign accumulated into `T`, e.g. `42`, `-42`
+/// or `+42`.
+///
+/// Overflowing `T` is a parse error at the offending digit rather than a
+/// panic or a wrapped value. Pick a wider `T` for inputs that need it with
+/// `integer_of::<i128, _>()`.
+pub fn integer_of<T, Input>() -> impl Parser<Input, Output = T>
+where
+    Input: Stream<Token = char>,
+    T: Integer,
+{
+    optional(one_of("+-".chars()))
+        .then(|sign| digits(sign == Some('-'), sign.is_some()))
+        .expected("integer")
+}
+
+/// An unsigned [`u64`], e.g. `42`.
+pub fn unsigned<Input>() -> impl Parser<Input, Output = u64>
+where
+    Input: Stream<Token = char>,
+{
+    unsigned_of()
+}
+
+/// A signed [`i64`], e.g. `42`, `-42` or `+42`.
+pub fn integer<Input>() -> impl Parser<Input, Output = i64>
+where
+    Input: Stream<Token = char>,
+{
+    integer_of()
+}
+
+/// One or more spaces or tabs.
+pub fn blanks<Input>() -> impl Parser<Input, Output = ()>
+where
+    Input: Stream<Token = char>,
+{
+    skip_many1(one_of(" \t".chars())).expected("whitespace")
+}
+
+/// A row of `item`s separated by runs of spaces or tabs, e.g. `3   4`,
+/// trailing blanks are allowed.
+pub fn row<Input, P>(item: P) -> impl Parser<Input, Output = Vec<P::Output>>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    // blanks only separate items when the line goes on, so an item missing
+    // after them is still reported as such
+    let separator = attempt(blanks().skip(look_ahead(none_of("\r\n".chars()))));
+    sep_by1(item, separator).skip(optional(blanks()))
+}
+
+/// A list of `item`s separated by `delimiter`, e.g. `75,47,61`.
+pub fn delimited<Input, P>(item: P, delimiter: char) -> impl Parser<Input, Output = Vec<P::Output>>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    sep_by1(item, char(delimiter))
+}
+
+/// A `\n` or `\r\n` line ending.
+pub fn line_ending<Input>() -> impl Parser<Input, Output = char>
+where
+    Input: Stream<Token = char>,
+{
+    crlf().or(newline()).expected("line ending")
+}
+
+/// One or more lines each parsed by `line`, the last line ending is
+/// optional.
+pub fn lines<Input, P>(line: P) -> impl Parser<Input, Output = Vec<P::Output>>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    sep_end_by1(line, line_ending())
+}
+
+/// An empty line, separating the sections of an input.
+pub fn blank_line<Input>() -> impl Parser<Input, Output = char>
+where
+    Input: Stream<Token = char>,
+{
+    line_ending().expected("blank line")
+}
+
+/// Sections of lines parsed by `line`, separated by blank lines.
+pub fn sections<Input, P>(line: P) -> impl Parser<Input, Output = Vec<Vec<P::Output>>>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    sep_by1(lines(line), blank_line())
+}
+
+/// A row of a [`char_grid`], failing where it is not `width` characters
+/// wide when that is known.
+fn grid_row<Input>(width: Option<usize>) -> impl Parser<Input, Output = Vec<char>>
+where
+    Input: Stream<Token = char>,
+{
+    parser(move |input: &mut Input| {
+        let start = input.position();
+        let mut row = vec![];
+        let mut beyond = None;
+        loop {
+            let checkpoint = input.checkpoint();
+            let position = input.position();
+            match input.uncons() {
+                Ok(c) if c != '\r' && c != '\n' => {
+                    if Some(row.len()) == width {
+                        beyond.get_or_insert(position);
+                    }
+                    row.push(c);
+                }
+                _ => {
+                    input
+                        .reset(checkpoint)
+                        .map_err(|err| Commit::Commit(err.into()))?;
+                    break;
+                }
+            }
+        }
+        if row.is_empty() {
+            return Err(Commit::Peek(Input::Error::empty(start).into()));
+        }
+        match width {
+            Some(width) if row.len() != width => {
+                let err = StreamErrorFor::<Input>::message_format(format_args!(
+                    "expected {} columns like line 1, found {}",
+                    width,
+                    row.len()
+                ));
+                let position = beyond.unwrap_or_else(|| input.position());
+                Err(Commit::Commit(
+                    Input::Error::from_error(position, err).into(),
+                ))
+            }
+            _ => Ok((row, Commit::Commit(()))),
+        }
+    })
+    .expected("grid row")
+}
+
+/// A rectangular grid of characters, one row per line, failing at a row
+/// that is not as wide as the first one.
+pub fn char_grid<Input>() -> impl Parser<Input, Output = Vec<Vec<char>>>
+where
+    Input: Stream<Token = char>,
+{
+    grid_row(None)
+        .then(|first: Vec<char>| {
+            let width = first.len();
+            // a line ending followed by a blank line ends the grid
+            let next_line = attempt(line_ending().skip(look_ahead(none_of("\r\n".chars()))));
+            many(next_line.with(grid_row(Some(width)))).map(move |rows: Vec<_>| {
+                let mut grid = vec![first.clone()];
+                grid.extend(rows);
+                grid
+            })
+        })
+        .skip(optional(line_ending()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `parse_all` with the error as its message, for comparing
+    fn parse<'a, P>(parser: P, input: &'a str) -> Result<P::Output, String>
+    where
+        P: Parser<PositionStream<'a>>,
+    {
+        parse_all(parser, input).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn integer_takes_an_optional_sign() {
+        assert_eq!(parse(integer(), "42"), Ok(42));
+        assert_eq!(parse(integer(), "+42"), Ok(42));
+        assert_eq!(parse(integer(), "-42"), Ok(-42));
+        assert_eq!(parse(integer(), "-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(
+            parse(integer(), "-"),
+            Err("line 1, column 2: unexpected end of input, expected digit".to_owned())
+        );
+        assert_eq!(
+            parse(integer(), "x"),
+            Err("line 1, column 1: unexpected `x`, expected integer".to_owned())
+        );
+    }
+
+    #[test]
+    fn integer_overflow_points_at_the_digit() {
+        assert_eq!(
+            parse(integer(), "9223372036854775808"),
+            Err("line 1, column 19: integer does not fit in i64".to_owned())
+        );
+        assert_eq!(
+            parse(row(integer()), "1 -9223372036854775809"),
+            Err("line 1, column 22: integer does not fit in i64".to_owned())
+        );
+    }
+
+    #[test]
+    fn integer_of_accumulates_into_the_chosen_type() {
+        assert_eq!(
+            parse(integer_of::<i128, _>(), "-9223372036854775809"),
+            Ok(i128::from(i64::MIN) - 1)
+        );
+        assert_eq!(
+            parse(integer_of::<i8, _>(), "128"),
+            Err("line 1, column 3: integer does not fit in i8".to_owned())
+        );
+        assert_eq!(parse(unsigned_of::<u8, _>(), "255"), Ok(255));
+    }
+
+    #[test]
+    fn unsigned_rejects_a_sign() {
+        assert_eq!(parse(unsigned(), "18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(
+            parse(unsigned(), "18446744073709551616"),
+            Err("line 1, column 20: integer does not fit in u64".to_owned())
+        );
+        assert_eq!(
+            parse(unsigned(), "-1"),
+            Err("line 1, column 1: unexpected `-`, expected unsigned integer".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_all_skips_a_bom_and_trailing_blank_lines() {
+        assert_eq!(parse(unsigned(), "\u{feff}42 \r\n\n\t\n"), Ok(42));
+        assert_eq!(
+            parse(unsigned(), "\u{feff}42 x"),
+            Err("line 1, column 4: unexpected `x`, expected blank line or end of input".to_owned())
+        );
+    }
+
+    #[test]
+    fn row_splits_on_runs_of_blanks() {
+        assert_eq!(parse(row(integer()), "3   4\t-5  "), Ok(vec![3, 4, -5]));
+        assert_eq!(
+            parse(row(integer()), "3   x"),
+            Err("line 1, column 5: unexpected `x`, expected integer".to_owned())
+        );
+    }
+
+    #[test]
+    fn delimited_splits_on_the_delimiter() {
+        assert_eq!(
+            parse(delimited(integer(), ','), "75,47,61"),
+            Ok(vec![75, 47, 61])
+        );
+        assert_eq!(
+            parse(delimited(integer(), ','), "75,,61"),
+            Err("line 1, column 4: unexpected `,`, expected integer".to_owned())
+        );
+    }
+
+    #[test]
+    fn lines_accept_both_line_endings() {
+        assert_eq!(parse(lines(unsigned()), "1\n2\r\n3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse(lines(unsigned()), "1\n2\n"), Ok(vec![1, 2]));
+        assert_eq!(
+            parse(lines(unsigned()), "1\n2x\n"),
+            Err("line 2, column 2: unexpected `x`, expected blank line or end of input".to_owned())
+        );
+    }
+
+    #[test]
+    fn line_ending_is_labelled() {
+        assert_eq!(parse(line_ending(), "\r\n"), Ok('\n'));
+        assert_eq!(
+            parse(line_ending(), "x"),
+            Err("line 1, column 1: unexpected `x`, expected line ending".to_owned())
+        );
+    }
+
+    #[test]
+    fn blank_line_separates_two_parts() {
+        let parts = (lines(unsigned()).skip(blank_line()), lines(unsigned()));
+        assert_eq!(parse(parts, "1\n2\n\n3\n"), Ok((vec![1, 2], vec![3])));
+        let parts = (lines(unsigned()).skip(blank_line()), lines(unsigned()));
+        assert_eq!(
+            parse(parts, "1\n2\n"),
+            Err("line 3, column 1: unexpected end of input, expected blank line".to_owned())
+        );
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() {
+        assert_eq!(
+            parse(sections(unsigned()), "1\n2\n\n3\n\n4\n"),
+            Ok(vec![vec![1, 2], vec![3], vec![4]])
+        );
+        // only a single blank line separates sections
+        assert_eq!(
+            parse(sections(unsigned()), "1\n\n\n3\n"),
+            Err("line 3, column 1: unexpected `\\n`, expected unsigned integer".to_owned())
+        );
+    }
+
+    #[test]
+    fn char_grid_keeps_rows_apart() {
+        assert_eq!(
+            parse(char_grid(), "ab\r\ncd\n"),
+            Ok(vec![vec!['a', 'b'], vec!['c', 'd']])
+        );
+        assert!(parse(char_grid(), "").is_err());
+    }
+
+    #[test]
+    fn char_grid_rejects_ragged_rows() {
+        assert_eq!(
+            parse(char_grid(), "XMAS\nXM\n"),
+            Err("line 2, column 3: expected 4 columns like line 1, found 2".to_owned())
+        );
+        assert_eq!(
+            parse(char_grid(), "XM\nXM\nXMAS"),
+            Err("line 3, column 3: expected 2 columns like line 1, found 4".to_owned())
+        );
+        assert_eq!(
+            parse(char_grid(), "ab\ncd\n\n"),
+            Ok(vec![vec!['a', 'b'], vec!['c', 'd']])
+        );
+    }
+
+    #[test]
+    fn line_reader_skips_blank_lines() {
+        let mut lines = LineReader::new("\u{feff}1 2\r\n \n\n3 x\n".as_bytes());
+        let line = lines.next_line().unwrap().unwrap();
+        assert_eq!((line.number, line.text, line.after_blank), (1, "1 2", None));
+        assert_eq!(line.parse(row(unsigned())).unwrap(), vec![1, 2]);
+
+        let line = lines.next_line().unwrap().unwrap();
+        assert_eq!(
+            (line.number, line.text, line.after_blank),
+            (4, "3 x", Some(2))
+        );
+        let err = line.parse(row(unsigned())).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 4, column 3: unexpected `x`, expected unsigned integer\n  |\n4 | 3 x\n  |   ^"
+        );
+        assert!(lines.next_line().unwrap().is_none());
+    }
+}
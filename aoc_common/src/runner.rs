@@ -0,0 +1,347 @@
+use crate::bench::{bench, bench_with, print_report, BenchFn, BenchResult};
+use crate::output::print_results;
+use crate::{Answer, Error, InputArgs, Result, RunArgs, Solution};
+use clap::ValueEnum;
+use std::fmt;
+use std::fs::{read_to_string, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// which part of a day to run
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Part {
+    /// the selected part, or both when none is selected
+    pub fn selected(part: Option<Part>) -> Vec<Part> {
+        match part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pt{}", self.number())
+    }
+}
+
+/// which input file a part is solved on
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+impl InputKind {
+    pub const ALL: [InputKind; 2] = [InputKind::Example, InputKind::Real];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputKind::Example => "example",
+            InputKind::Real => "real",
+        }
+    }
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// outcome of solving one part of a day on one input
+#[derive(Debug)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub kind: InputKind,
+    pub answer: Result<Answer>,
+    /// how long solving the part took, if it got to run at all
+    pub duration: Option<Duration>,
+}
+
+impl PartResult {
+    /// The result with `f` applied to its error, e.g. to attach the input
+    /// it happened in.
+    pub fn map_err(self, f: impl FnOnce(Error) -> Error) -> PartResult {
+        PartResult {
+            answer: self.answer.map_err(f),
+            ..self
+        }
+    }
+
+    /// results of the selected parts of an input that could not be solved
+    fn failed(day: u8, kind: InputKind, part: Option<Part>, err: &Error) -> Vec<PartResult> {
+        Part::selected(part)
+            .into_iter()
+            .map(|part| PartResult {
+                day,
+                part,
+                kind,
+                answer: Err(err.clone()),
+                duration: None,
+            })
+            .collect()
+    }
+}
+
+/// Type-erased [`solve`], so days can be dispatched at runtime.
+pub type SolveFn = fn(InputKind, &str, Option<Part>) -> Result<Vec<PartResult>>;
+
+/// Parses an input of a day and solves the requested part (or both) on it.
+///
+/// Only a failure to parse is returned as an error, failing parts are
+/// reported in their [`PartResult`].
+pub fn solve<S: Solution>(
+    kind: InputKind,
+    input_str: &str,
+    part: Option<Part>,
+) -> Result<Vec<PartResult>> {
+    solve_with::<S>(kind, input_str, part, &S::Options::default())
+}
+
+/// [`solve`] with the day's options.
+pub fn solve_with<S: Solution>(
+    kind: InputKind,
+    input_str: &str,
+    part: Option<Part>,
+    options: &S::Options,
+) -> Result<Vec<PartResult>> {
+    let input = S::parse_input(input_str).map_err(|err| err.with_day(S::DAY))?;
+    Ok(Part::selected(part)
+        .into_iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::pt1(&input, options),
+                Part::Two => S::pt2(&input, options),
+            };
+            PartResult {
+                day: S::DAY,
+                part,
+                kind,
+                answer: answer.map_err(|err| err.with_day(S::DAY)),
+                duration: Some(start.elapsed()),
+            }
+        })
+        .collect())
+}
+
+/// A day's entry points, so the `aoc` runner can dispatch days at runtime.
+#[derive(Clone, Copy)]
+pub struct DaySolver {
+    pub day: u8,
+    pub solve: SolveFn,
+    pub bench: BenchFn,
+}
+
+impl DaySolver {
+    pub fn of<S: Solution>() -> DaySolver {
+        DaySolver {
+            day: S::DAY,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+}
+
+/// Reads and parses the example and real input of a day, then runs the
+/// requested part (or both) on each of them.
+///
+/// Inputs that cannot be read or parsed and failing parts are printed, and
+/// make the returned exit code [`ExitCode::FAILURE`].
+pub fn run<S: Solution>(
+    paths: &InputArgs,
+    args: &RunArgs,
+) -> std::result::Result<ExitCode, Box<dyn std::error::Error>> {
+    run_with::<S>(paths, args, &S::Options::default())
+}
+
+/// [`run`] with the day's options.
+pub fn run_with<S: Solution>(
+    paths: &InputArgs,
+    args: &RunArgs,
+    options: &S::Options,
+) -> std::result::Result<ExitCode, Box<dyn std::error::Error>> {
+    match args.bench {
+        Some(runs) => {
+            let bench =
+                |kind, input_str: &str, part| bench_with::<S>(kind, input_str, part, runs, options);
+            match bench_inputs(paths, args, bench) {
+                Ok(results) => {
+                    print_report(&results, args.bench_format)?;
+                    Ok(ExitCode::SUCCESS)
+                }
+                Err(err) => {
+                    eprintln!("{:#}", err.with_day(S::DAY));
+                    Ok(ExitCode::FAILURE)
+                }
+            }
+        }
+        None => {
+            let solve =
+                |kind, input_str: &str, part| solve_with::<S>(kind, input_str, part, options);
+            print_results(&solve_inputs(S::DAY, paths, args, solve), args.format)
+        }
+    }
+}
+
+/// The input a day's own report modes work on: the real input unless `only`
+/// selects the example.
+pub fn selected_input(paths: &InputArgs, only: Option<InputKind>) -> (InputKind, &Path) {
+    match only {
+        Some(InputKind::Example) => (InputKind::Example, &paths.example_path),
+        _ => (InputKind::Real, &paths.input_path),
+    }
+}
+
+/// Reads and parses the input selected by `only`, see [`selected_input`],
+/// with the day, file and position attached to errors.
+pub fn read_parsed<S: Solution>(paths: &InputArgs, only: Option<InputKind>) -> Result<S::Input> {
+    let (_, path) = selected_input(paths, only);
+    let input_str = read_input(path).map_err(|err| Error::from(err).with_file(path.display()))?;
+    S::parse_input(&input_str)
+        .map_err(|err| err.with_day(S::DAY).with_input(path.display(), &input_str))
+}
+
+/// Reads an input file, `-` reads stdin instead.
+pub fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input_str = String::new();
+        io::stdin().read_to_string(&mut input_str)?;
+        Ok(input_str)
+    } else {
+        read_to_string(path)
+    }
+}
+
+/// Opens an input file for reading line by line, `-` reads stdin instead.
+pub fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+/// Reads the inputs of a day selected by `only`, along with the path each
+/// was read from.
+///
+/// The example is optional: when it does not exist and was not explicitly
+/// selected it is skipped.
+fn read_inputs(
+    paths: &InputArgs,
+    only: Option<InputKind>,
+) -> Result<Vec<(InputKind, &Path, Result<String>)>> {
+    if only.is_none()
+        && paths.example_path == paths.input_path
+        && paths.input_path == Path::new("-")
+    {
+        return Err("stdin can only be used for one of the example and the input".into());
+    }
+    let mut inputs = vec![];
+    if only != Some(InputKind::Real) {
+        let path = paths.example_path.as_path();
+        match read_input(path) {
+            Ok(input_str) => inputs.push((InputKind::Example, path, Ok(input_str))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && only.is_none() => {}
+            Err(err) => inputs.push((
+                InputKind::Example,
+                path,
+                Err(Error::from(err).with_file(path.display())),
+            )),
+        }
+    }
+    if only != Some(InputKind::Example) {
+        let path = paths.input_path.as_path();
+        let input_str = read_input(path).map_err(|err| Error::from(err).with_file(path.display()));
+        inputs.push((InputKind::Real, path, input_str));
+    }
+    Ok(inputs)
+}
+
+/// Solves the requested part (or both) of a day looked up at runtime on
+/// each of its inputs.
+///
+/// Inputs that cannot be read or parsed are reported as failed results.
+pub fn run_solver(solver: DaySolver, paths: &InputArgs, args: &RunArgs) -> Vec<PartResult> {
+    solve_inputs(solver.day, paths, args, solver.solve)
+}
+
+/// Solves each input of a day with `solve`, see [`run_solver`].
+fn solve_inputs(
+    day: u8,
+    paths: &InputArgs,
+    args: &RunArgs,
+    solve: impl Fn(InputKind, &str, Option<Part>) -> Result<Vec<PartResult>>,
+) -> Vec<PartResult> {
+    let inputs = match read_inputs(paths, args.only) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            let kind = args.only.unwrap_or(InputKind::Real);
+            let err = err.with_day(day);
+            return PartResult::failed(day, kind, args.part, &err);
+        }
+    };
+    let mut results = vec![];
+    for (kind, path, input_str) in inputs {
+        let solved = input_str.and_then(|input_str| {
+            let with_input = |err: Error| err.with_input(path.display(), &input_str);
+            let solved = solve(kind, &input_str, args.part).map_err(with_input)?;
+            Ok(solved
+                .into_iter()
+                .map(|result| result.map_err(with_input))
+                .collect::<Vec<_>>())
+        });
+        match solved {
+            Ok(solved) => results.extend(solved),
+            Err(err) => {
+                let err = err.with_day(day);
+                results.extend(PartResult::failed(day, kind, args.part, &err))
+            }
+        }
+    }
+    results
+}
+
+/// Benchmarks the requested part (or both) of a day looked up at runtime.
+pub fn bench_solver(
+    solver: DaySolver,
+    paths: &InputArgs,
+    args: &RunArgs,
+    runs: u32,
+) -> Result<Vec<BenchResult>> {
+    let bench = |kind, input_str: &str, part| (solver.bench)(kind, input_str, part, runs);
+    bench_inputs(paths, args, bench)
+}
+
+/// Benchmarks each input of a day with `bench`, see [`bench_solver`].
+fn bench_inputs(
+    paths: &InputArgs,
+    args: &RunArgs,
+    bench: impl Fn(InputKind, &str, Option<Part>) -> Result<Vec<BenchResult>>,
+) -> Result<Vec<BenchResult>> {
+    let mut results = vec![];
+    for (kind, path, input_str) in read_inputs(paths, args.only)? {
+        let input_str = input_str?;
+        let bench = bench(kind, &input_str, args.part);
+        results.extend(bench.map_err(|err| err.with_input(path.display(), &input_str))?);
+    }
+    Ok(results)
+}
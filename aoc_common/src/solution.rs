@@ -0,0 +1,27 @@
+use crate::{Answer, Result};
+use std::fmt::Debug;
+
+/// A single day of the advent calendar.
+///
+/// Every day parses its puzzle input once and then solves both parts on the
+/// parsed representation.
+pub trait Solution {
+    /// day of the month this solution belongs to
+    const DAY: u8;
+
+    /// parsed puzzle input
+    type Input: Debug;
+
+    /// options changing how the parts are solved, `()` for days without any
+    ///
+    /// The `aoc` runner always solves with the defaults, a day's own binary
+    /// can take them from its command line and pass them to
+    /// [`run_with`](crate::run_with).
+    type Options: Default;
+
+    fn parse_input(input: &str) -> Result<Self::Input>;
+
+    fn pt1(input: &Self::Input, options: &Self::Options) -> Result<Answer>;
+
+    fn pt2(input: &Self::Input, options: &Self::Options) -> Result<Answer>;
+}
@@ -0,0 +1,75 @@
+//! Times the similarity score implementations against each other on
+//! synthetic location lists, checking they agree.
+//!
+//! `cargo bench -p day_01 --bench similarity`
+
+use aoc_common::{Accumulator, SeededRng};
+use day_01::{similarity_counting, similarity_naive, similarity_sorted_merge};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+type Similarity = fn(&[i64], &[i64], Accumulator) -> aoc_common::Result<i128>;
+
+const SIZES: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+/// the naive score is quadratic, larger lists take minutes
+const NAIVE_MAX: usize = 10_000;
+const RUNS: u32 = 5;
+
+/// Two lists of `len` numbers in the range of the real input, from a fixed
+/// seed so every run times the same lists.
+fn location_lists(len: usize) -> (Vec<i64>, Vec<i64>) {
+    let mut rng = SeededRng::new(0x2024_1201);
+    let mut next = || 10_000 + rng.below(90_000) as i64;
+    let first = (0..len).map(|_| next()).collect();
+    let second = (0..len).map(|_| next()).collect();
+    (first, second)
+}
+
+/// median time and answer of `runs` runs
+fn time(similarity: Similarity, first: &[i64], second: &[i64]) -> (Duration, i128) {
+    let mut samples = vec![];
+    let mut score = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        score = black_box(similarity(
+            black_box(first),
+            black_box(second),
+            Accumulator::I64,
+        ))
+        .unwrap();
+        samples.push(start.elapsed());
+    }
+    samples.sort();
+    (samples[samples.len() / 2], score)
+}
+
+fn main() {
+    let variants: [(&str, Similarity); 3] = [
+        ("naive", similarity_naive),
+        ("counting", similarity_counting),
+        ("sorted-merge", similarity_sorted_merge),
+    ];
+
+    println!(
+        "{:>9} {:<13} {:>12} {:>16}",
+        "lines", "variant", "median", "score"
+    );
+    for len in SIZES {
+        let (first, second) = location_lists(len);
+        let mut expected = None;
+        for (name, similarity) in variants {
+            if name == "naive" && len > NAIVE_MAX {
+                continue;
+            }
+            let (median, score) = time(similarity, &first, &second);
+            assert_eq!(*expected.get_or_insert(score), score, "{} disagrees", name);
+            println!(
+                "{:>9} {:<13} {:>12} {:>16}",
+                len,
+                name,
+                format!("{:.2?}", median),
+                score
+            );
+        }
+    }
+}
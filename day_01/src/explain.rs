@@ -0,0 +1,239 @@
+use crate::{similarity_counting, total_distance, InputStruct, Metric, Options};
+use aoc_common::{ExplainFormat, Part, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// a pair of ids, with the 1-based input lines they are on
+#[derive(Debug, Clone, Serialize)]
+pub struct Pair {
+    /// 0-based rank of the ids in their sorted lists
+    pub rank: usize,
+    pub first: i64,
+    pub first_line: usize,
+    pub second: i64,
+    pub second_line: usize,
+    pub distance: i64,
+}
+
+/// what one id of the first list adds to the similarity score
+#[derive(Debug, Clone, Serialize)]
+pub struct Contribution {
+    /// 1-based input line
+    pub line: usize,
+    pub id: i64,
+    /// times the id is in the second list
+    pub count: usize,
+    pub contribution: i64,
+}
+
+/// ids of a list with their 1-based lines, smallest first
+fn sorted_with_lines(list: &[i64]) -> Vec<(i64, usize)> {
+    let mut sorted: Vec<_> = list.iter().copied().zip(1..).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+/// The ids of two lists paired smallest with smallest, with their distance
+/// under `metric`.
+pub(crate) fn pairs(first: &[i64], second: &[i64], metric: Metric) -> Result<Vec<Pair>> {
+    sorted_with_lines(first)
+        .into_iter()
+        .zip(sorted_with_lines(second))
+        .enumerate()
+        .map(|(rank, ((first, first_line), (second, second_line)))| {
+            let distance = metric
+                .distance(rank, first, second)
+                .ok_or_else(|| format!("distance of {} and {} overflows i64", first, second))?;
+            Ok(Pair {
+                rank,
+                first,
+                first_line,
+                second,
+                second_line,
+                distance,
+            })
+        })
+        .collect()
+}
+
+/// The pairs of lists, 1-based, whose score is an answer of `part`: the
+/// distance is symmetric so part 1 has every unordered pair, part 2 every
+/// ordered one.
+fn list_pairs(input: &InputStruct, part: Part) -> Vec<(usize, usize)> {
+    let lists = input.location_lists.lists.len();
+    (1..=lists)
+        .flat_map(|first| (1..=lists).map(move |second| (first, second)))
+        .filter(|&(first, second)| match part {
+            Part::One => first < second,
+            Part::Two => first != second && (lists > 2 || first < second),
+        })
+        .collect()
+}
+
+/// The pairs behind the total distance of lists `first` and `second`,
+/// 1-based.
+pub fn explain_distance(
+    input: &InputStruct,
+    (first, second): (usize, usize),
+    metric: Metric,
+) -> Result<Vec<Pair>> {
+    let lists = &input.location_lists.lists;
+    pairs(&lists[first - 1], &lists[second - 1], metric)
+}
+
+/// The contribution of every id of list `first` to its similarity score with
+/// list `second`, 1-based, in input order.
+pub fn explain_similarity(
+    input: &InputStruct,
+    (first, second): (usize, usize),
+) -> Result<Vec<Contribution>> {
+    let lists = &input.location_lists.lists;
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for id in &lists[second - 1] {
+        *counts.entry(*id).or_default() += 1;
+    }
+    lists[first - 1]
+        .iter()
+        .zip(1..)
+        .map(|(&id, line)| {
+            let count = counts.get(&id).copied().unwrap_or(0);
+            let contribution = i64::try_from(count)
+                .ok()
+                .and_then(|count| count.checked_mul(id))
+                .ok_or("similarity score overflows i64")?;
+            Ok(Contribution {
+                line,
+                id,
+                count,
+                contribution,
+            })
+        })
+        .collect()
+}
+
+/// Prints the pairs behind part 1 and the contributions behind part 2 of
+/// every pair of lists, or those of the selected part.
+///
+/// CSV has a single header, so it needs a part selected.
+pub fn print_explanation(
+    input: &InputStruct,
+    part: Option<Part>,
+    options: &Options,
+    format: ExplainFormat,
+) -> Result<()> {
+    let metric = options.metric;
+    if format == ExplainFormat::Csv && part.is_none() {
+        return Err("explaining as CSV needs a part selected with --part".into());
+    }
+    let lists = &input.location_lists.lists;
+    for part in Part::selected(part) {
+        if format == ExplainFormat::Csv {
+            match part {
+                Part::One => println!("lists,rank,first,first_line,second,second_line,distance"),
+                Part::Two => println!("lists,line,id,count,contribution"),
+            }
+        }
+        for (first, second) in list_pairs(input, part) {
+            match (part, format) {
+                (Part::One, ExplainFormat::Text) => {
+                    println!("pt1 lists {} and {}", first, second);
+                    println!(
+                        "{:>8} {:>12} {:>8} {:>12} {:>8} {:>12}",
+                        "rank", "first", "line", "second", "line", "distance"
+                    );
+                    for pair in explain_distance(input, (first, second), metric)? {
+                        println!(
+                            "{:>8} {:>12} {:>8} {:>12} {:>8} {:>12}",
+                            pair.rank,
+                            pair.first,
+                            pair.first_line,
+                            pair.second,
+                            pair.second_line,
+                            pair.distance
+                        );
+                    }
+                    let (first_list, second_list) = (&lists[first - 1], &lists[second - 1]);
+                    let total =
+                        total_distance(first_list, second_list, metric, options.accumulator)?;
+                    println!("total {}", total);
+                }
+                (Part::One, ExplainFormat::Csv) => {
+                    for pair in explain_distance(input, (first, second), metric)? {
+                        println!(
+                            "{}-{},{},{},{},{},{},{}",
+                            first,
+                            second,
+                            pair.rank,
+                            pair.first,
+                            pair.first_line,
+                            pair.second,
+                            pair.second_line,
+                            pair.distance
+                        );
+                    }
+                }
+                (Part::Two, ExplainFormat::Text) => {
+                    println!("pt2 lists {} and {}", first, second);
+                    println!(
+                        "{:>8} {:>12} {:>8} {:>16}",
+                        "line", "id", "count", "contribution"
+                    );
+                    for contribution in explain_similarity(input, (first, second))? {
+                        println!(
+                            "{:>8} {:>12} {:>8} {:>16}",
+                            contribution.line,
+                            contribution.id,
+                            contribution.count,
+                            contribution.contribution
+                        );
+                    }
+                    let (first_list, second_list) = (&lists[first - 1], &lists[second - 1]);
+                    let score = similarity_counting(first_list, second_list, options.accumulator)?;
+                    println!("score {}", score);
+                }
+                (Part::Two, ExplainFormat::Csv) => {
+                    for contribution in explain_similarity(input, (first, second))? {
+                        println!(
+                            "{}-{},{},{},{},{}",
+                            first,
+                            second,
+                            contribution.line,
+                            contribution.id,
+                            contribution.count,
+                            contribution.contribution
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn pairs_add_up_to_the_total_distance() {
+        let input = crate::parse_input(EXAMPLE).unwrap();
+        let pairs = explain_distance(&input, (1, 2), Metric::Absolute).unwrap();
+        assert_eq!(pairs.iter().map(|pair| pair.distance).sum::<i64>(), 11);
+        // the smallest ids, 1 and 3, are on lines 4 and 2
+        assert_eq!((pairs[0].first_line, pairs[0].second_line), (4, 2));
+    }
+
+    #[test]
+    fn contributions_add_up_to_the_similarity_score() {
+        let input = crate::parse_input(EXAMPLE).unwrap();
+        let contributions = explain_similarity(&input, (1, 2)).unwrap();
+        let counts: Vec<_> = contributions.iter().map(|c| c.count).collect();
+        assert_eq!(counts, [3, 1, 0, 0, 3, 3]);
+        assert_eq!(
+            contributions.iter().map(|c| c.contribution).sum::<i64>(),
+            31
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{add_similarity, check_columns, location_row, matrix_answer, Metric, Options};
+use aoc_common::parse::{LineReader, ParseError};
+use aoc_common::{Accumulator, Answer, InputKind, Part, PartResult, Result, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// A directory of spilled runs and sorted lists, removed again on drop.
+#[derive(Debug)]
+struct SpillDir {
+    path: PathBuf,
+}
+
+impl SpillDir {
+    fn create(temp_dir: &Path) -> io::Result<SpillDir> {
+        // unique per process and per sort within it
+        static SORTS: AtomicUsize = AtomicUsize::new(0);
+        let path = temp_dir.join(format!(
+            "day_01-{}-{}",
+            process::id(),
+            SORTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(SpillDir { path })
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Ids of a list file in ascending order, stored as little-endian `i64`s.
+struct SortedIds {
+    reader: BufReader<File>,
+    peeked: Option<i64>,
+}
+
+impl SortedIds {
+    fn open(path: &Path) -> io::Result<SortedIds> {
+        let mut ids = SortedIds {
+            reader: BufReader::new(File::open(path)?),
+            peeked: None,
+        };
+        ids.peeked = ids.read()?;
+        Ok(ids)
+    }
+
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        let mut bytes = [0; 8];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Ok(Some(i64::from_le_bytes(bytes))),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn peek(&self) -> Option<i64> {
+        self.peeked
+    }
+
+    fn next(&mut self) -> io::Result<Option<i64>> {
+        let id = self.peeked;
+        self.peeked = self.read()?;
+        Ok(id)
+    }
+
+    /// Skips the ids equal to `id`, returning how many there were.
+    fn skip_equal(&mut self, id: i64) -> io::Result<usize> {
+        let mut count = 0;
+        while self.peek() == Some(id) {
+            self.next()?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+fn write_ids(path: &Path, ids: impl IntoIterator<Item = i64>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for id in ids {
+        writer.write_all(&id.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// The lists of an input sorted on disk, one file per list.
+#[derive(Debug)]
+pub struct SortedLists {
+    lists: Vec<PathBuf>,
+    _dir: SpillDir,
+}
+
+/// Reads location rows line by line and sorts every list with at most
+/// `run_size` ids of each in memory, spilling sorted runs to `temp_dir` and
+/// merging them.
+///
+/// Accepts the same inputs as the in-memory parser.
+pub fn sort_external(
+    reader: impl BufRead,
+    run_size: usize,
+    temp_dir: &Path,
+) -> Result<SortedLists> {
+    let dir = SpillDir::create(temp_dir)?;
+    let mut runs: Vec<Vec<PathBuf>> = vec![];
+    let mut buffers: Vec<Vec<i64>> = vec![];
+    let spill = |runs: &mut Vec<Vec<PathBuf>>, buffers: &mut Vec<Vec<i64>>| -> io::Result<()> {
+        for (list, buffer) in buffers.iter_mut().enumerate() {
+            buffer.sort_unstable();
+            let path = dir
+                .path
+                .join(format!("list{}-run{}", list, runs[list].len()));
+            write_ids(&path, buffer.drain(..))?;
+            runs[list].push(path);
+        }
+        Ok(())
+    };
+
+    let mut lines = LineReader::new(reader);
+    while let Some(line) = lines.next_line()? {
+        if let Some(blank_line) = line.after_blank {
+            let message = format!("unexpected ids after the blank line {}", blank_line);
+            return Err(line.error(ParseError::at_offset(line.text, 0, message)));
+        }
+        let ids = line.parse(location_row())?;
+        let first = buffers.is_empty();
+        let columns = if first { ids.len() } else { buffers.len() };
+        check_columns(line.number, columns, ids.len()).map_err(|err| line.error(err))?;
+
+        if first {
+            buffers = vec![Vec::with_capacity(run_size.min(1 << 20)); ids.len()];
+            runs = vec![vec![]; ids.len()];
+        }
+        for (buffer, id) in buffers.iter_mut().zip(ids) {
+            buffer.push(id);
+        }
+        if buffers[0].len() >= run_size {
+            spill(&mut runs, &mut buffers)?;
+        }
+    }
+    if buffers.is_empty() {
+        return Err(ParseError {
+            line: 1,
+            column: 1,
+            message: "expected location ids".to_owned(),
+        }
+        .into());
+    }
+    if !buffers[0].is_empty() {
+        spill(&mut runs, &mut buffers)?;
+    }
+
+    let lists = runs
+        .into_iter()
+        .enumerate()
+        .map(|(list, runs)| {
+            let path = dir.path.join(format!("list{}", list));
+            merge_runs(&runs, &path)?;
+            Ok(path)
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(SortedLists { lists, _dir: dir })
+}
+
+/// k-way merges sorted run files into one sorted file, removing the runs.
+fn merge_runs(runs: &[PathBuf], path: &Path) -> io::Result<()> {
+    let mut readers = runs
+        .iter()
+        .map(|run| SortedIds::open(run))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut heap: BinaryHeap<_> = readers
+        .iter()
+        .enumerate()
+        .filter_map(|(run, ids)| Some(Reverse((ids.peek()?, run))))
+        .collect();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    while let Some(Reverse((id, run))) = heap.pop() {
+        writer.write_all(&id.to_le_bytes())?;
+        readers[run].next()?;
+        if let Some(next) = readers[run].peek() {
+            heap.push(Reverse((next, run)));
+        }
+    }
+    writer.flush()?;
+
+    for run in runs {
+        fs::remove_file(run)?;
+    }
+    Ok(())
+}
+
+impl SortedLists {
+    fn open(&self, list: usize) -> io::Result<SortedIds> {
+        SortedIds::open(&self.lists[list])
+    }
+
+    /// [`total_distance`](crate::total_distance) of two lists, streamed
+    /// from disk.
+    pub fn total_distance(
+        &self,
+        first: usize,
+        second: usize,
+        metric: Metric,
+        accumulator: Accumulator,
+    ) -> Result<i128> {
+        let (mut first, mut second) = (self.open(first)?, self.open(second)?);
+        let mut total = 0i128;
+        let mut rank = 0;
+        while let (Some(a), Some(b)) = (first.next()?, second.next()?) {
+            total = metric
+                .distance(rank, a, b)
+                .and_then(|distance| metric.total(total, distance))
+                .and_then(|total| accumulator.fit(total))
+                .ok_or_else(|| format!("total distance overflows {}", accumulator))?;
+            rank += 1;
+        }
+        Ok(total)
+    }
+
+    /// [`similarity_sorted_merge`](crate::similarity_sorted_merge) of two
+    /// lists, streamed from disk.
+    pub fn similarity(
+        &self,
+        first: usize,
+        second: usize,
+        accumulator: Accumulator,
+    ) -> Result<i128> {
+        let (mut first, mut second) = (self.open(first)?, self.open(second)?);
+        let mut score = 0;
+        while let Some(id) = first.peek() {
+            let count = first.skip_equal(id)?;
+            while second.peek().is_some_and(|other| other < id) {
+                second.next()?;
+            }
+            let count = count * second.skip_equal(id)?;
+            score = add_similarity(score, id, count, accumulator)?;
+        }
+        Ok(score)
+    }
+
+    /// `matrix[i][j]` of `score(i, j)` for every pair of lists
+    fn pairwise(&self, score: impl Fn(usize, usize) -> Result<i128>) -> Result<Vec<Vec<i128>>> {
+        (0..self.lists.len())
+            .map(|first| {
+                (0..self.lists.len())
+                    .map(|second| score(first, second))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn pt1(&self, options: &Options) -> Result<Answer> {
+        let total_distance =
+            |first, second| self.total_distance(first, second, options.metric, options.accumulator);
+        Ok(matrix_answer(self.pairwise(total_distance)?))
+    }
+
+    pub fn pt2(&self, options: &Options) -> Result<Answer> {
+        let similarity = |first, second| self.similarity(first, second, options.accumulator);
+        Ok(matrix_answer(self.pairwise(similarity)?))
+    }
+}
+
+/// [`solve_with`](aoc_common::solve_with) for inputs larger than memory:
+/// sorts the lists read from `reader` on disk, see [`sort_external`], and
+/// solves the requested part (or both) on them.
+pub fn solve_external(
+    reader: impl BufRead,
+    kind: InputKind,
+    part: Option<Part>,
+    options: &Options,
+    run_size: usize,
+    temp_dir: &Path,
+) -> Result<Vec<PartResult>> {
+    let lists = sort_external(reader, run_size, temp_dir)?;
+    Ok(Part::selected(part)
+        .into_iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => lists.pt1(options),
+                Part::Two => lists.pt2(options),
+            };
+            PartResult {
+                day: crate::Day01::DAY,
+                part,
+                kind,
+                answer: answer.map_err(|err| err.with_day(crate::Day01::DAY)),
+                duration: Some(start.elapsed()),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn matches_in_memory_answers_across_runs() {
+        for run_size in [1, 2, 4, 100] {
+            let lists = sort_external(EXAMPLE.as_bytes(), run_size, &std::env::temp_dir()).unwrap();
+            assert_eq!(lists.pt1(&Options::default()).unwrap(), Answer::Int(11));
+            assert_eq!(lists.pt2(&Options::default()).unwrap(), Answer::Int(31));
+        }
+    }
+
+    #[test]
+    fn removes_spilled_runs() {
+        let lists = sort_external(EXAMPLE.as_bytes(), 2, &std::env::temp_dir()).unwrap();
+        let dir = lists._dir.path.clone();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        drop(lists);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        let err =
+            sort_external("3   4\n4   3   2\n".as_bytes(), 2, &std::env::temp_dir()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 2 columns like line 1, found 3"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::InputStruct;
+use aoc_common::Result;
+use std::ops::Range;
+
+/// one of the two lists of an [`IncrementalLists`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+/// Two location lists kept sorted, with their total distance and similarity
+/// score updated on every insert and remove instead of recomputed.
+///
+/// Inserting or removing an id re-pairs the ids after it, so costs are linear
+/// in the ids after its position at worst. [`replace`](Self::replace), the
+/// usual tweak of an id, only re-pairs the ids between its old and new
+/// position. The similarity score is updated in `O(log n)`.
+#[derive(Debug, Clone)]
+pub struct IncrementalLists {
+    first: Vec<i64>,
+    second: Vec<i64>,
+    /// kept wider than the answers, so updates never overflow halfway
+    distance: i128,
+    similarity: i128,
+}
+
+impl IncrementalLists {
+    pub fn new(mut first: Vec<i64>, mut second: Vec<i64>) -> IncrementalLists {
+        first.sort_unstable();
+        second.sort_unstable();
+        let mut lists = IncrementalLists {
+            first,
+            second,
+            distance: 0,
+            similarity: 0,
+        };
+        lists.distance = lists.pair_distances(0..lists.first.len());
+        lists.similarity = lists
+            .first
+            .iter()
+            .map(|&id| i128::from(id) * lists.count(Side::Second, id) as i128)
+            .sum();
+        lists
+    }
+
+    /// The lists of a parsed input, which has to have exactly two of them.
+    pub fn from_input(input: &InputStruct) -> Result<IncrementalLists> {
+        match input.location_lists.lists.as_slice() {
+            [first, second] => Ok(IncrementalLists::new(first.clone(), second.clone())),
+            lists => Err(format!("expected 2 location lists, found {}", lists.len()).into()),
+        }
+    }
+
+    /// the ids of a side, smallest first
+    pub fn list(&self, side: Side) -> &[i64] {
+        match side {
+            Side::First => &self.first,
+            Side::Second => &self.second,
+        }
+    }
+
+    fn list_mut(&mut self, side: Side) -> &mut Vec<i64> {
+        match side {
+            Side::First => &mut self.first,
+            Side::Second => &mut self.second,
+        }
+    }
+
+    /// number of times `id` is in a side
+    pub fn count(&self, side: Side, id: i64) -> usize {
+        let list = self.list(side);
+        list.partition_point(|&x| x <= id) - list.partition_point(|&x| x < id)
+    }
+
+    /// sum of the distances of the pairs at `ranks`, ignoring ranks past the
+    /// shorter list
+    fn pair_distances(&self, ranks: Range<usize>) -> i128 {
+        let paired = self.first.len().min(self.second.len());
+        let ranks = ranks.start.min(paired)..ranks.end.min(paired);
+        self.first[ranks.clone()]
+            .iter()
+            .zip(&self.second[ranks])
+            .map(|(first, second)| i128::from(first.abs_diff(*second)))
+            .sum()
+    }
+
+    /// similarity contributed by one `id` of `side` paired with the counts of
+    /// the other side
+    fn similarity_of(&self, side: Side, id: i64) -> i128 {
+        let other = match side {
+            Side::First => Side::Second,
+            Side::Second => Side::First,
+        };
+        i128::from(id) * self.count(other, id) as i128
+    }
+
+    /// Applies `update` to the ids of a side, which may only move the pairs
+    /// at `ranks` of both the old and the new lists.
+    fn update_distance(&mut self, ranks: Range<usize>, update: impl FnOnce(&mut Self)) {
+        let before = self.pair_distances(ranks.clone());
+        update(self);
+        self.distance += self.pair_distances(ranks) - before;
+    }
+
+    pub fn insert(&mut self, side: Side, id: i64) {
+        let rank = self.list(side).partition_point(|&x| x < id);
+        let end = self.first.len().max(self.second.len()) + 1;
+        self.update_distance(rank..end, |lists| lists.list_mut(side).insert(rank, id));
+        self.similarity += self.similarity_of(side, id);
+    }
+
+    /// Removes one `id` from a side, `false` if it was not in it.
+    pub fn remove(&mut self, side: Side, id: i64) -> bool {
+        let Ok(rank) = self.list(side).binary_search(&id) else {
+            return false;
+        };
+        let end = self.first.len().max(self.second.len());
+        self.update_distance(rank..end, |lists| {
+            lists.list_mut(side).remove(rank);
+        });
+        self.similarity -= self.similarity_of(side, id);
+        true
+    }
+
+    /// Replaces one `old` id of a side with `new`, `false` if `old` was not
+    /// in it.
+    pub fn replace(&mut self, side: Side, old: i64, new: i64) -> bool {
+        let Ok(old_rank) = self.list(side).binary_search(&old) else {
+            return false;
+        };
+        self.similarity -= self.similarity_of(side, old);
+        let list = self.list(side);
+        // rank of `new` once `old` is removed
+        let new_rank = list.partition_point(|&x| x < new) - usize::from(new > old);
+        let ranks = old_rank.min(new_rank)..old_rank.max(new_rank) + 1;
+        self.update_distance(ranks, |lists| {
+            let list = lists.list_mut(side);
+            list.remove(old_rank);
+            list.insert(new_rank, new);
+        });
+        self.similarity += self.similarity_of(side, new);
+        true
+    }
+
+    /// the total distance of the lists paired smallest with smallest
+    pub fn total_distance(&self) -> Result<i64> {
+        Ok(i64::try_from(self.distance).map_err(|_| "total distance overflows i64")?)
+    }
+
+    pub fn similarity(&self) -> Result<i64> {
+        Ok(i64::try_from(self.similarity).map_err(|_| "similarity score overflows i64")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{similarity_counting, total_distance, Metric};
+    use aoc_common::Accumulator;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[derive(Debug, Clone)]
+    enum Update {
+        Insert(Side, i64),
+        Remove(Side, i64),
+        Replace(Side, i64, i64),
+    }
+
+    fn updates() -> impl Strategy<Value = Vec<Update>> {
+        let side = || prop_oneof![Just(Side::First), Just(Side::Second)];
+        // a narrow range, so ids repeat and removes and replaces find them
+        let id = || -10i64..10;
+        let update = prop_oneof![
+            (side(), id()).prop_map(|(side, id)| Update::Insert(side, id)),
+            (side(), id()).prop_map(|(side, id)| Update::Remove(side, id)),
+            (side(), id(), id()).prop_map(|(side, old, new)| Update::Replace(side, old, new)),
+        ];
+        prop::collection::vec(update, 0..100)
+    }
+
+    /// the batch answers for the current lists
+    fn assert_matches_batch(lists: &IncrementalLists) {
+        let (first, second) = (lists.list(Side::First), lists.list(Side::Second));
+        assert_eq!(
+            i128::from(lists.total_distance().unwrap()),
+            total_distance(first, second, Metric::Absolute, Accumulator::I64).unwrap()
+        );
+        assert_eq!(
+            i128::from(lists.similarity().unwrap()),
+            similarity_counting(first, second, Accumulator::I64).unwrap()
+        );
+    }
+
+    #[test]
+    fn starts_with_the_example_answers() {
+        let input = crate::parse_input(EXAMPLE).unwrap();
+        let lists = IncrementalLists::from_input(&input).unwrap();
+        assert_eq!(lists.total_distance().unwrap(), 11);
+        assert_eq!(lists.similarity().unwrap(), 31);
+    }
+
+    #[test]
+    fn inserts_and_removes() {
+        let input = crate::parse_input(EXAMPLE).unwrap();
+        let mut lists = IncrementalLists::from_input(&input).unwrap();
+        lists.insert(Side::First, 3);
+        assert_matches_batch(&lists);
+        lists.insert(Side::Second, 0);
+        assert_matches_batch(&lists);
+        lists.insert(Side::Second, 10);
+        assert_matches_batch(&lists);
+        assert!(lists.remove(Side::First, 1));
+        assert_matches_batch(&lists);
+        assert!(!lists.remove(Side::First, 7));
+        assert_matches_batch(&lists);
+    }
+
+    #[test]
+    fn replaces_in_both_directions() {
+        let mut lists = IncrementalLists::new(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]);
+        for (side, old, new) in [
+            (Side::First, 1, 8),
+            (Side::First, 8, 0),
+            (Side::Second, 9, 3),
+            (Side::Second, 3, 3),
+            (Side::First, 3, 4),
+        ] {
+            assert!(lists.replace(side, old, new));
+            assert_matches_batch(&lists);
+        }
+        assert!(!lists.replace(Side::Second, 9, 1));
+    }
+
+    #[test]
+    fn rejects_more_than_two_lists() {
+        let input = crate::parse_input("1 2 3\n").unwrap();
+        assert!(IncrementalLists::from_input(&input).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn matches_batch_over_updates(
+            first in prop::collection::vec(-10i64..10, 0..30),
+            second in prop::collection::vec(-10i64..10, 0..30),
+            updates in updates(),
+        ) {
+            let mut lists = IncrementalLists::new(first, second);
+            for update in updates {
+                match update {
+                    Update::Insert(side, id) => lists.insert(side, id),
+                    Update::Remove(side, id) => {
+                        lists.remove(side, id);
+                    }
+                    Update::Replace(side, old, new) => {
+                        lists.replace(side, old, new);
+                    }
+                }
+                assert_matches_batch(&lists);
+            }
+        }
+    }
+}
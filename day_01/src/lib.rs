@@ -0,0 +1,438 @@
+use aoc_common::parse::{integer, lines, parse_all, row, ParseError};
+use aoc_common::{Accumulator, Answer, Result, Solution};
+use clap::{Args, ValueEnum};
+use combine::stream::Stream;
+use combine::Parser;
+use std::collections::HashMap;
+
+mod explain;
+mod external;
+mod incremental;
+mod stats;
+
+pub use explain::{explain_distance, explain_similarity, print_explanation, Contribution, Pair};
+pub use external::{solve_external, sort_external, SortedLists};
+pub use incremental::{IncrementalLists, Side};
+pub use stats::{distance_stats, print_stats, Bucket, DistanceStats};
+
+#[derive(Debug)]
+pub struct InputStruct {
+    location_lists: LocationLists,
+}
+
+/// the columns of the input, every one a list of location ids
+#[derive(Debug)]
+struct LocationLists {
+    lists: Vec<Vec<i64>>,
+}
+
+/// how the distance between two paired ids is measured and totalled
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Metric {
+    /// sum of `|a - b|`
+    #[default]
+    Absolute,
+    /// sum of `(a - b)²`
+    Squared,
+    /// largest `|a - b|`
+    Chebyshev,
+    /// sum of `|a - b|` times the 1-based rank of the pair, so differences
+    /// between the larger ids weigh more
+    Weighted,
+}
+
+impl Metric {
+    /// distance of the pair of the `rank`th smallest ids, 0-based, `None`
+    /// when it does not fit in an i64
+    pub fn distance(self, rank: usize, first: i64, second: i64) -> Option<i64> {
+        let diff = i64::try_from(first.abs_diff(second)).ok()?;
+        match self {
+            Metric::Absolute | Metric::Chebyshev => Some(diff),
+            Metric::Squared => diff.checked_mul(diff),
+            Metric::Weighted => i64::try_from(rank + 1).ok()?.checked_mul(diff),
+        }
+    }
+
+    /// `total` with one more pair's `distance` added
+    fn total(self, total: i128, distance: i64) -> Option<i128> {
+        match self {
+            Metric::Chebyshev => Some(total.max(distance.into())),
+            _ => total.checked_add(distance.into()),
+        }
+    }
+}
+
+/// day 1 options
+#[derive(Args, Debug, Clone, Default)]
+pub struct Options {
+    /// how the distance between paired location ids is measured
+    #[arg(long, value_enum, default_value_t)]
+    pub metric: Metric,
+
+    /// integer type the answers are totalled in
+    #[arg(long, value_enum, default_value_t)]
+    pub accumulator: Accumulator,
+}
+
+fn parse_input(input: &str) -> Result<InputStruct> {
+    let rows = parse_all(location_rows(), input)?;
+    Ok(InputStruct {
+        location_lists: separate_lists(rows)?,
+    })
+}
+
+fn location_row<Input>() -> impl Parser<Input, Output = Vec<i64>>
+where
+    Input: Stream<Token = char>,
+{
+    row(integer())
+}
+
+fn location_rows<Input>() -> impl Parser<Input, Output = Vec<Vec<i64>>>
+where
+    Input: Stream<Token = char>,
+{
+    lines(location_row())
+}
+
+/// Splits the rows into one list per column, failing on the first row with a
+/// different number of columns than the first one.
+fn separate_lists(rows: Vec<Vec<i64>>) -> std::result::Result<LocationLists, ParseError> {
+    let columns = rows[0].len();
+    let mut lists = vec![Vec::with_capacity(rows.len()); columns];
+    for (line_nr, row) in rows.into_iter().enumerate() {
+        check_columns(line_nr + 1, columns, row.len())?;
+        for (list, id) in lists.iter_mut().zip(row) {
+            list.push(id);
+        }
+    }
+
+    Ok(LocationLists { lists })
+}
+
+/// Fails unless line `line_nr`, 1-based, has as many columns as line 1 and
+/// there are at least two.
+fn check_columns(
+    line_nr: usize,
+    columns: usize,
+    found: usize,
+) -> std::result::Result<(), ParseError> {
+    let message = if columns < 2 {
+        format!("expected at least 2 location lists, found {}", columns)
+    } else if found != columns {
+        format!("expected {} columns like line 1, found {}", columns, found)
+    } else {
+        return Ok(());
+    };
+    Err(ParseError {
+        line: line_nr as i32,
+        column: 1,
+        message,
+    })
+}
+
+/// `matrix[i][j]` of `score(list i, list j)` for every pair of lists
+fn pairwise(
+    location_lists: &LocationLists,
+    score: impl Fn(&[i64], &[i64]) -> Result<i128>,
+) -> Result<Vec<Vec<i128>>> {
+    let lists = &location_lists.lists;
+    lists
+        .iter()
+        .map(|first| lists.iter().map(|second| score(first, second)).collect())
+        .collect()
+}
+
+/// The score of the only pair for two lists, the whole matrix for more.
+fn matrix_answer(matrix: Vec<Vec<i128>>) -> Answer {
+    if matrix.len() == 2 {
+        return matrix[0][1].into();
+    }
+    let width = matrix
+        .iter()
+        .flatten()
+        .map(|score| score.to_string().len())
+        .max()
+        .unwrap_or(0);
+    Answer::Grid(
+        matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|score| format!("{:>width$}", score, width = width))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect(),
+    )
+}
+
+fn pt1(input: &InputStruct, options: &Options) -> Result<Answer> {
+    let total_distance = |first: &[i64], second: &[i64]| {
+        total_distance(first, second, options.metric, options.accumulator)
+    };
+    Ok(matrix_answer(pairwise(
+        &input.location_lists,
+        total_distance,
+    )?))
+}
+
+fn pt2(input: &InputStruct, options: &Options) -> Result<Answer> {
+    let similarity =
+        |first: &[i64], second: &[i64]| similarity_counting(first, second, options.accumulator);
+    Ok(matrix_answer(pairwise(&input.location_lists, similarity)?))
+}
+
+/// Total of the distances between the smallest ids of both lists, the
+/// second smallest and so on, failing when it does not fit in `accumulator`.
+pub fn total_distance(
+    first: &[i64],
+    second: &[i64],
+    metric: Metric,
+    accumulator: Accumulator,
+) -> Result<i128> {
+    let mut first = first.to_vec();
+    let mut second = second.to_vec();
+    first.sort_unstable();
+    second.sort_unstable();
+
+    let total = first
+        .iter()
+        .zip(second.iter())
+        .enumerate()
+        .try_fold(0i128, |total, (rank, (first, second))| {
+            metric
+                .distance(rank, *first, *second)
+                .and_then(|distance| metric.total(total, distance))
+                .and_then(|total| accumulator.fit(total))
+        })
+        .ok_or_else(|| format!("total distance overflows {}", accumulator))?;
+
+    Ok(total)
+}
+
+/// `score + nr * count`, failing instead of overflowing `accumulator`
+fn add_similarity(score: i128, nr: i64, count: usize, accumulator: Accumulator) -> Result<i128> {
+    i128::try_from(count)
+        .ok()
+        .and_then(|count| count.checked_mul(nr.into()))
+        .and_then(|similarity| score.checked_add(similarity))
+        .and_then(|score| accumulator.fit(score))
+        .ok_or_else(|| format!("similarity score overflows {}", accumulator).into())
+}
+
+/// Similarity score by counting every number of `first` in all of `second`,
+/// O(n²).
+pub fn similarity_naive(first: &[i64], second: &[i64], accumulator: Accumulator) -> Result<i128> {
+    first.iter().try_fold(0, |score, nr| {
+        let count = second.iter().filter(|&x| x == nr).count();
+        add_similarity(score, *nr, count, accumulator)
+    })
+}
+
+/// Similarity score from a frequency map of `second`, O(n).
+pub fn similarity_counting(
+    first: &[i64],
+    second: &[i64],
+    accumulator: Accumulator,
+) -> Result<i128> {
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for nr in second {
+        *counts.entry(*nr).or_default() += 1;
+    }
+    first.iter().try_fold(0, |score, nr| {
+        add_similarity(
+            score,
+            *nr,
+            counts.get(nr).copied().unwrap_or(0),
+            accumulator,
+        )
+    })
+}
+
+/// Similarity score by walking both lists sorted, O(n log n) without
+/// hashing.
+pub fn similarity_sorted_merge(
+    first: &[i64],
+    second: &[i64],
+    accumulator: Accumulator,
+) -> Result<i128> {
+    let mut first = first.to_vec();
+    let mut second = second.to_vec();
+    first.sort_unstable();
+    second.sort_unstable();
+
+    let mut score = 0;
+    let mut j = 0;
+    for run in first.chunk_by(|a, b| a == b) {
+        let nr = run[0];
+        while j < second.len() && second[j] < nr {
+            j += 1;
+        }
+        let start = j;
+        while j < second.len() && second[j] == nr {
+            j += 1;
+        }
+        score = add_similarity(score, nr, run.len() * (j - start), accumulator)?;
+    }
+
+    Ok(score)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = InputStruct;
+    type Options = Options;
+
+    fn parse_input(input: &str) -> Result<InputStruct> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &InputStruct, options: &Options) -> Result<Answer> {
+        pt1(input, options)
+    }
+
+    fn pt2(input: &InputStruct, options: &Options) -> Result<Answer> {
+        pt2(input, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    /// the example's lists, however they were laid out
+    fn assert_parses_example(input: &str) {
+        let input = parse_input(input).unwrap();
+        assert_eq!(pt1(&input, &Options::default()).unwrap(), Answer::Int(11));
+        assert_eq!(pt2(&input, &Options::default()).unwrap(), Answer::Int(31));
+    }
+
+    #[test]
+    fn parses_example() {
+        assert_parses_example(EXAMPLE);
+    }
+
+    #[test]
+    fn parses_tabs_and_runs_of_blanks() {
+        assert_parses_example(&EXAMPLE.replace("   ", "\t"));
+        assert_parses_example(&EXAMPLE.replace("   ", " \t  "));
+    }
+
+    #[test]
+    fn parses_crlf() {
+        assert_parses_example(&EXAMPLE.replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn parses_missing_final_newline() {
+        assert_parses_example(EXAMPLE.trim_end());
+    }
+
+    #[test]
+    fn parses_trailing_blank_lines() {
+        assert_parses_example(&format!("{}\n\n  \r\n\n", EXAMPLE));
+    }
+
+    #[test]
+    fn parses_trailing_blanks() {
+        assert_parses_example(&EXAMPLE.replace('\n', "  \n"));
+    }
+
+    #[test]
+    fn parses_byte_order_mark() {
+        assert_parses_example(&format!("\u{feff}{}", EXAMPLE));
+    }
+
+    #[test]
+    fn metrics() {
+        let input = parse_input(EXAMPLE).unwrap();
+        let total = |metric| {
+            let options = Options {
+                metric,
+                ..Options::default()
+            };
+            pt1(&input, &options).unwrap()
+        };
+        assert_eq!(total(Metric::Squared), Answer::Int(35));
+        assert_eq!(total(Metric::Chebyshev), Answer::Int(5));
+        assert_eq!(total(Metric::Weighted), Answer::Int(48));
+    }
+
+    #[test]
+    fn rejects_inconsistent_columns() {
+        let err = parse_input("3   4\n4   3   2\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 2 columns like line 1, found 3"
+        );
+    }
+
+    #[test]
+    fn scores_every_pair_of_more_lists() {
+        // the example with a third list of 1 to 6
+        let rows: Vec<_> = EXAMPLE
+            .lines()
+            .zip(1..)
+            .map(|(line, id)| format!("{}   {}", line, id))
+            .collect();
+        let input = parse_input(&rows.join("\n")).unwrap();
+        let grid = |rows: [&str; 3]| Answer::Grid(rows.map(str::to_owned).to_vec());
+        assert_eq!(
+            pt1(&input, &Options::default()).unwrap(),
+            grid([" 0 11  5", "11  0  6", " 5  6  0"])
+        );
+        assert_eq!(
+            pt2(&input, &Options::default()).unwrap(),
+            grid(["34 31 16", "31 45 18", "16 18 21"])
+        );
+    }
+
+    #[test]
+    fn totals_beyond_i64_in_i128() {
+        let max = i64::MAX;
+        let input = parse_input(&format!("0 {0}\n0 {0}\n{0} {0}\n", max)).unwrap();
+        let wide = Options {
+            accumulator: Accumulator::I128,
+            ..Options::default()
+        };
+        let twice_max = (2 * i128::from(max)).to_string();
+
+        let err = pt1(&input, &Options::default()).unwrap_err();
+        assert_eq!(err.to_string(), "total distance overflows i64");
+        assert_eq!(pt1(&input, &wide).unwrap(), Answer::Text(twice_max));
+
+        let err = pt2(&input, &Options::default()).unwrap_err();
+        assert_eq!(err.to_string(), "similarity score overflows i64");
+        let thrice_max = (3 * i128::from(max)).to_string();
+        assert_eq!(pt2(&input, &wide).unwrap(), Answer::Text(thrice_max));
+    }
+
+    #[test]
+    fn rejects_a_single_list() {
+        let err = parse_input("3\n4\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected at least 2 location lists, found 1"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn similarity_variants_agree(
+            // a narrow range, so ids repeat within and across the lists
+            first in prop::collection::vec(-20i64..20, 0..50),
+            second in prop::collection::vec(-20i64..20, 0..50),
+        ) {
+            let accumulator = Accumulator::I64;
+            let naive = similarity_naive(&first, &second, accumulator).unwrap();
+            prop_assert_eq!(similarity_counting(&first, &second, accumulator).unwrap(), naive);
+            prop_assert_eq!(similarity_sorted_merge(&first, &second, accumulator).unwrap(), naive);
+        }
+    }
+}
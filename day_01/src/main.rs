@@ -1,123 +1,111 @@
-use clap::Parser as ArgParser;
-use combine::parser::char::{digit, newline, string};
-use combine::parser::repeat::sep_by1;
-use combine::stream::Stream;
-use combine::Parser;
-use combine::{many1, EasyParser};
-use std::fs::read_to_string;
-
-#[derive(Debug)]
-struct InputStruct {
-    location_lists: Vec<Vec<i64>>,
-}
-
-#[derive(Debug)]
-struct LocationLists {
-    first: Vec<i64>,
-    second: Vec<i64>,
-}
+use aoc_common::{
+    open_input, print_results, read_parsed, selected_input, Error, ExplainFormat, InputArgs,
+    InputKind, RunArgs, Solution,
+};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser as ArgParser};
+use day_01::{distance_stats, print_explanation, print_stats, solve_external, Day01, Options};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 #[derive(ArgParser)]
 #[command(author, version, about)]
 /// cli arguments
 struct Args {
-    /// example file path
-    #[arg(long, env = "EXAMPLE_FILE", default_value = "example.txt")]
-    example_path: std::path::PathBuf,
-
-    /// input file path
-    #[arg(long, env = "INPUT_FILE", default_value = "input.txt")]
-    input_path: std::path::PathBuf,
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    run: RunArgs,
+
+    #[command(flatten)]
+    options: Options,
+
+    /// print statistics of the distances between the paired ids instead of
+    /// solving, of the real input unless `--only example`
+    #[arg(long)]
+    stats: bool,
+
+    /// print the pairs behind part 1 and the contributions behind part 2
+    /// instead of solving, of the real input unless `--only example`
+    #[arg(long, conflicts_with_all = ["stats", "bench"])]
+    explain: bool,
+
+    /// how `--explain` prints, CSV needs `--part`
+    #[arg(long, value_enum, default_value_t)]
+    explain_format: ExplainFormat,
+
+    /// sort the lists on disk to solve inputs larger than memory, the real
+    /// input unless `--only example`
+    #[arg(long, conflicts_with_all = ["stats", "explain", "bench"])]
+    external: bool,
+
+    /// ids of each list sorted in memory at a time by `--external`
+    #[arg(long, default_value = "4194304")]
+    run_size: NonZeroUsize,
+
+    /// directory `--external` spills sorted runs to, the system's temporary
+    /// directory by default
+    #[arg(long)]
+    temp_dir: Option<PathBuf>,
 }
 
-fn parse_input(
-    input: &str,
-) -> std::result::Result<
-    InputStruct,
-    combine::easy::Errors<char, String, combine::stream::PointerOffset<str>>,
-> {
-    let result = (two_ints_list())
-        .map(|location_lists| InputStruct { location_lists })
-        .easy_parse(input);
-
-    match result {
-        Ok((result, _rest)) => Ok(result),
-        Err(err) => Err(err.map_range(|err: &str| err.to_owned())),
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    if args.explain && args.explain_format == ExplainFormat::Csv && args.run.part.is_none() {
+        Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "`--explain-format csv` needs `--part`",
+            )
+            .exit();
+    }
+    if !args.stats && !args.explain && !args.external {
+        return aoc_common::run_with::<Day01>(&args.input, &args.run, &args.options);
     }
-}
-
-fn two_ints<Input>() -> impl Parser<Input, Output = Vec<i64>>
-where
-    Input: Stream<Token = char>,
-{
-    sep_by1(integer_parser(), string("   "))
-}
-
-fn two_ints_list<Input>() -> impl Parser<Input, Output = Vec<Vec<i64>>>
-where
-    Input: Stream<Token = char>,
-{
-    many1::<Vec<Vec<i64>>, _, _>(two_ints().skip(newline()))
-}
-
-fn integer_parser<Input>() -> impl Parser<Input, Output = i64>
-where
-    Input: Stream<Token = char>,
-{
-    // Parse one or more digits, then combine them into an integer
-    many1(digit()).map(|digits: String| digits.parse::<i64>().unwrap())
-}
 
-fn separate_lists(input: &InputStruct) -> LocationLists {
-    let mut location_lists = LocationLists {
-        first: vec![],
-        second: vec![],
+    let (kind, path) = selected_input(&args.input, args.run.only);
+    let result = if args.stats {
+        stats(&args).map(|()| ExitCode::SUCCESS)
+    } else if args.explain {
+        explain(&args).map(|()| ExitCode::SUCCESS)
+    } else {
+        external(&args, kind, path)
     };
-    for line in &input.location_lists {
-        location_lists.first.push(line[0]);
-        location_lists.second.push(line[1]);
+    match result {
+        Ok(exit_code) => Ok(exit_code),
+        Err(err) => {
+            eprintln!("{:#}", err.with_day(Day01::DAY).with_file(path.display()));
+            Ok(ExitCode::FAILURE)
+        }
     }
-
-    location_lists
 }
 
-fn pt1(input: &InputStruct) -> Result<(), Box<dyn std::error::Error>> {
-    let mut location_lists = separate_lists(input);
-    location_lists.first.sort();
-    location_lists.second.sort();
-
-    let diff: Vec<i64> = location_lists
-        .first
-        .iter()
-        .zip(location_lists.second.iter())
-        .map(|(first, second)| (first - second).abs())
-        .collect();
-    let total: i64 = diff.iter().sum();
-    println!("total diff: {:#?}", total);
-
-    Ok(())
+fn stats(args: &Args) -> aoc_common::Result<()> {
+    let input = read_parsed::<Day01>(&args.input, args.run.only)?;
+    let stats = distance_stats(&input, args.options.metric)?;
+    print_stats(&stats, args.run.format).map_err(|err| Error::from(err.to_string()))
 }
 
-fn pt2(input: &InputStruct) -> Result<(), Box<dyn std::error::Error>> {
-    let location_lists = separate_lists(input);
-    let mut sim_score = 0;
-    for nr in location_lists.first.iter() {
-        let tot = location_lists.second.iter().filter(|&&x| x == *nr).count();
-        sim_score += tot as i64 * *nr;
-    }
-
-    println!("{:#?}", sim_score);
-    Ok(())
+fn explain(args: &Args) -> aoc_common::Result<()> {
+    let input = read_parsed::<Day01>(&args.input, args.run.only)?;
+    print_explanation(&input, args.run.part, &args.options, args.explain_format)
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
-    let example_input_str = read_to_string(args.example_path)?;
-    let real_input_str = read_to_string(args.input_path)?;
-    let example_input = parse_input(example_input_str.as_str())?;
-    let input = parse_input(real_input_str.as_str())?;
-    println!("{:#?}", example_input);
-    let _ = pt1(&input);
-    let _ = pt2(&input);
-    Ok(())
+fn external(args: &Args, kind: InputKind, path: &Path) -> aoc_common::Result<ExitCode> {
+    let reader = open_input(path)?;
+    let temp_dir = args.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+    let results: Vec<_> = solve_external(
+        reader,
+        kind,
+        args.run.part,
+        &args.options,
+        args.run_size.get(),
+        &temp_dir,
+    )?
+    .into_iter()
+    .map(|result| result.map_err(|err| err.with_file(path.display())))
+    .collect();
+    print_results(&results, args.run.format).map_err(|err| Error::from(err.to_string()))
 }
@@ -0,0 +1,198 @@
+use crate::explain::{pairs, Pair};
+use crate::{InputStruct, Metric};
+use aoc_common::{OutputFormat, Result};
+use serde::Serialize;
+use std::error::Error;
+
+/// number of equally wide buckets the distances are counted in
+const HISTOGRAM_BUCKETS: i64 = 10;
+/// number of largest distances reported
+const OUTLIERS: usize = 5;
+
+/// Distribution of the distances between the paired ids of two lists.
+#[derive(Debug, Clone, Serialize)]
+pub struct DistanceStats {
+    /// 1-based columns of the two lists
+    pub lists: (usize, usize),
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub max: i64,
+    pub histogram: Vec<Bucket>,
+    /// the pairs with the largest distances, largest first
+    pub outliers: Vec<Pair>,
+}
+
+/// number of distances in `from..=to`
+#[derive(Debug, Clone, Serialize)]
+pub struct Bucket {
+    pub from: i64,
+    pub to: i64,
+    pub count: usize,
+}
+
+/// Statistics of the distances under `metric` for every pair of lists.
+pub fn distance_stats(input: &InputStruct, metric: Metric) -> Result<Vec<DistanceStats>> {
+    let lists = &input.location_lists.lists;
+    let mut stats = vec![];
+    for i in 0..lists.len() {
+        for j in i + 1..lists.len() {
+            stats.push(pair_stats((i + 1, j + 1), &lists[i], &lists[j], metric)?);
+        }
+    }
+    Ok(stats)
+}
+
+fn pair_stats(
+    lists: (usize, usize),
+    first: &[i64],
+    second: &[i64],
+    metric: Metric,
+) -> Result<DistanceStats> {
+    let mut pairs = pairs(first, second, metric)?;
+
+    let count = pairs.len();
+    let mean = pairs.iter().map(|pair| pair.distance as f64).sum::<f64>() / count as f64;
+
+    // largest first, ties by rank
+    pairs.sort_by(|a, b| b.distance.cmp(&a.distance).then(a.rank.cmp(&b.rank)));
+    let max = pairs[0].distance;
+    let median = if count % 2 == 1 {
+        pairs[count / 2].distance as f64
+    } else {
+        (pairs[count / 2 - 1].distance as f64 + pairs[count / 2].distance as f64) / 2.0
+    };
+
+    let width = max / HISTOGRAM_BUCKETS + 1;
+    let mut histogram: Vec<_> = (0..HISTOGRAM_BUCKETS)
+        .map(|bucket| {
+            let from = bucket.saturating_mul(width);
+            Bucket {
+                from,
+                to: from.saturating_add(width - 1),
+                count: 0,
+            }
+        })
+        .take_while(|bucket| bucket.from <= max)
+        .collect();
+    for pair in &pairs {
+        histogram[(pair.distance / width) as usize].count += 1;
+    }
+
+    pairs.truncate(OUTLIERS);
+    Ok(DistanceStats {
+        lists,
+        count,
+        mean,
+        median,
+        max,
+        histogram,
+        outliers: pairs,
+    })
+}
+
+/// Prints the statistics of every pair of lists as text or as JSON.
+pub fn print_stats(
+    stats: &[DistanceStats],
+    format: OutputFormat,
+) -> std::result::Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Text => {
+            for stats in stats {
+                println!("lists {} and {}", stats.lists.0, stats.lists.1);
+                println!("  count   {}", stats.count);
+                println!("  mean    {:.2}", stats.mean);
+                println!("  median  {:.1}", stats.median);
+                println!("  max     {}", stats.max);
+                println!("  histogram");
+                let most = stats.histogram.iter().map(|bucket| bucket.count).max();
+                for bucket in &stats.histogram {
+                    let bar = bucket.count * 40 / most.unwrap_or(1).max(1);
+                    println!(
+                        "    {:>10} {:>8}  {}",
+                        format!("{}..={}", bucket.from, bucket.to),
+                        bucket.count,
+                        "#".repeat(bar)
+                    );
+                }
+                println!("  outliers");
+                for outlier in &stats.outliers {
+                    println!(
+                        "    rank {:<6} {} (line {}) and {} (line {}): {}",
+                        outlier.rank,
+                        outlier.first,
+                        outlier.first_line,
+                        outlier.second,
+                        outlier.second_line,
+                        outlier.distance
+                    );
+                }
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(stats)?),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    fn example_stats(metric: Metric) -> DistanceStats {
+        let input = crate::parse_input(EXAMPLE).unwrap();
+        let mut stats = distance_stats(&input, metric).unwrap();
+        assert_eq!(stats.len(), 1);
+        stats.remove(0)
+    }
+
+    fn counts(stats: &DistanceStats) -> Vec<(i64, i64, usize)> {
+        stats
+            .histogram
+            .iter()
+            .map(|bucket| (bucket.from, bucket.to, bucket.count))
+            .collect()
+    }
+
+    #[test]
+    fn describes_the_example() {
+        // distances 2, 1, 0, 1, 2 and 5 by rank
+        let stats = example_stats(Metric::Absolute);
+        assert_eq!(stats.lists, (1, 2));
+        assert_eq!(stats.count, 6);
+        assert_eq!(format!("{:.2}", stats.mean), "1.83");
+        assert_eq!(stats.median, 1.5);
+        assert_eq!(stats.max, 5);
+        assert_eq!(
+            counts(&stats),
+            [
+                (0, 0, 1),
+                (1, 1, 2),
+                (2, 2, 2),
+                (3, 3, 0),
+                (4, 4, 0),
+                (5, 5, 1)
+            ]
+        );
+        let outliers: Vec<_> = stats
+            .outliers
+            .iter()
+            .map(|pair| (pair.rank, pair.distance))
+            .collect();
+        assert_eq!(outliers, [(5, 5), (0, 2), (4, 2), (1, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn buckets_wider_distances() {
+        // distances 4, 1, 0, 1, 4 and 25, in buckets 3 wide
+        let stats = example_stats(Metric::Squared);
+        assert_eq!(stats.median, 2.5);
+        let counts = counts(&stats);
+        assert_eq!(counts.len(), 9);
+        assert_eq!(counts[0], (0, 2, 3));
+        assert_eq!(counts[1], (3, 5, 2));
+        assert_eq!(counts[8], (24, 26, 1));
+        assert_eq!(counts.iter().map(|bucket| bucket.2).sum::<usize>(), 6);
+    }
+}
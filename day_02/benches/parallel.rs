@@ -0,0 +1,89 @@
+//! Times both parts evaluated sequentially and split across threads on a
+//! large synthetic input, checking every thread count gives the same
+//! answers.
+//!
+//! `cargo bench -p day_02 --bench parallel`
+
+use aoc_common::{bench_with, InputKind, SeededRng, Solution, Stage};
+use day_02::{Day02, Options};
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::Duration;
+
+const REPORTS: usize = 1_000_000;
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+/// tolerances of part 2, the larger one takes the quadratic path
+const TOLERANCES: [usize; 2] = [1, 2];
+const RUNS: u32 = 5;
+
+/// `REPORTS` reports of 5 to 8 levels, mostly small steps so that safe,
+/// dampened and unsafe reports all show up, from a fixed seed so every run
+/// times the same input.
+fn reports() -> String {
+    let mut rng = SeededRng::new(0x2024_1202);
+    let mut next = |modulo| rng.below(modulo);
+    let mut input = String::new();
+    for _ in 0..REPORTS {
+        let len = 5 + next(4);
+        let direction = if next(2) == 0 { 1 } else { -1 };
+        let mut level = 10 + next(80) as i64;
+        let levels: Vec<_> = (0..len)
+            .map(|_| {
+                level += direction * (next(4) as i64 + if next(10) == 0 { 3 } else { 0 });
+                level.to_string()
+            })
+            .collect();
+        input.push_str(&levels.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+fn main() {
+    let input_str = reports();
+    let input = Day02::parse_input(&input_str).unwrap();
+    let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    println!("{} reports, {} cores", REPORTS, cores);
+
+    println!(
+        "{:<4} {:>9} {:>7} {:>12} {:>8}",
+        "part", "tolerance", "threads", "median", "answer"
+    );
+    for tolerance in TOLERANCES {
+        let mut expected = [None, None];
+        for threads in THREADS {
+            let options = Options {
+                tolerance,
+                threads: NonZeroUsize::new(threads).unwrap(),
+                ..Options::default()
+            };
+            let answers = [
+                Day02::pt1(&input, &options).unwrap(),
+                Day02::pt2(&input, &options).unwrap(),
+            ];
+            let results =
+                bench_with::<Day02>(InputKind::Real, &input_str, None, RUNS, &options).unwrap();
+            let parts = results.iter().filter(|result| result.stage != Stage::Parse);
+            for ((expected, answer), result) in expected.iter_mut().zip(answers).zip(parts) {
+                let part = match result.stage {
+                    Stage::Pt1 => "pt1",
+                    _ => "pt2",
+                };
+                let expected = expected.get_or_insert_with(|| answer.clone());
+                assert_eq!(
+                    *expected, answer,
+                    "{} with {} threads disagrees",
+                    part, threads
+                );
+                println!(
+                    "{:<4} {:>9} {:>7} {:>12} {:>8}",
+                    part,
+                    tolerance,
+                    threads,
+                    format!("{:.2?}", Duration::from_nanos(result.median_ns as u64)),
+                    answer
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{InputStruct, Options, Violation};
+use aoc_common::ExplainFormat;
+
+/// why a report is safe or not in either part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportExplanation {
+    /// 1-based input line
+    pub line: usize,
+    /// safe in part 1
+    pub safe: bool,
+    /// the first step breaking the rule, if not safe
+    pub violation: Option<Violation>,
+    /// safe in part 2
+    pub safe_pt2: bool,
+    /// 0-based indices of the levels part 2 removes to make the report
+    /// safe, empty if it is safe already or cannot be made safe
+    pub removed: Vec<usize>,
+}
+
+/// Why every report is safe or not, in input order.
+pub fn explain_reports(input: &InputStruct, options: &Options) -> Vec<ReportExplanation> {
+    input
+        .reports
+        .iter()
+        .zip(1..)
+        .map(|(report, line)| {
+            let violation = options.rule.first_violation(report);
+            let mut removed = match violation {
+                Some(_) => options.rule.removed_levels(report),
+                None => vec![],
+            };
+            let safe_pt2 = removed.len() <= options.tolerance;
+            if !safe_pt2 {
+                removed.clear();
+            }
+            ReportExplanation {
+                line,
+                safe: violation.is_none(),
+                violation,
+                safe_pt2,
+                removed,
+            }
+        })
+        .collect()
+}
+
+/// Prints why every report is safe or not in part 1 and part 2.
+pub fn print_explanation(input: &InputStruct, options: &Options, format: ExplainFormat) {
+    let explanations = explain_reports(input, options);
+    let removed = |explanation: &ReportExplanation| {
+        let removed: Vec<_> = explanation.removed.iter().map(usize::to_string).collect();
+        removed.join(" ")
+    };
+    match format {
+        ExplainFormat::Text => {
+            println!(
+                "{:>8} {:>5} {:>6}  {:<16} {:>5}  removed",
+                "line", "safe", "index", "rule", "pt2"
+            );
+            for explanation in &explanations {
+                let (index, rule) = match explanation.violation {
+                    Some(violation) => (violation.index.to_string(), violation.rule.to_string()),
+                    None => (String::new(), String::new()),
+                };
+                let row = format!(
+                    "{:>8} {:>5} {:>6}  {:<16} {:>5}  {}",
+                    explanation.line,
+                    explanation.safe,
+                    index,
+                    rule,
+                    explanation.safe_pt2,
+                    removed(explanation)
+                );
+                println!("{}", row.trim_end());
+            }
+            let safe = explanations.iter().filter(|e| e.safe).count();
+            let safe_pt2 = explanations.iter().filter(|e| e.safe_pt2).count();
+            println!("safe {}, safe in pt2 {}", safe, safe_pt2);
+        }
+        ExplainFormat::Csv => {
+            println!("line,safe,index,rule,safe_pt2,removed");
+            for explanation in &explanations {
+                let (index, rule) = match explanation.violation {
+                    Some(violation) => (violation.index.to_string(), violation.rule.to_string()),
+                    None => (String::new(), String::new()),
+                };
+                println!(
+                    "{},{},{},{},{},{}",
+                    explanation.line,
+                    explanation.safe,
+                    index,
+                    rule,
+                    explanation.safe_pt2,
+                    removed(explanation)
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, RuleBreak};
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn explains_the_example() {
+        let input = parse_input(EXAMPLE).unwrap();
+        let explanations = explain_reports(&input, &Options::default());
+        let violations: Vec<_> = explanations
+            .iter()
+            .map(|e| e.violation.map(|v| (v.index, v.rule)))
+            .collect();
+        assert_eq!(
+            violations,
+            [
+                None,
+                Some((2, RuleBreak::StepTooLarge)),
+                Some((3, RuleBreak::StepTooLarge)),
+                Some((2, RuleBreak::DirectionFlip)),
+                Some((3, RuleBreak::ZeroStep)),
+                None,
+            ]
+        );
+        let removed: Vec<_> = explanations.iter().map(|e| e.removed.clone()).collect();
+        assert_eq!(removed, [vec![], vec![], vec![], vec![1], vec![2], vec![]]);
+        let safe_pt2: Vec<_> = explanations.iter().map(|e| e.safe_pt2).collect();
+        assert_eq!(safe_pt2, [true, false, false, true, true, true]);
+    }
+}
@@ -0,0 +1,447 @@
+use aoc_common::parse::{blanks, integer, lines, parse_all, row, ParseError};
+use aoc_common::{Answer, Result, Solution};
+use clap::Args;
+use combine::stream::Stream;
+use combine::{optional, Parser};
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::{panic, thread};
+
+mod explain;
+mod rule;
+mod stream;
+
+pub use explain::{explain_reports, print_explanation, ReportExplanation};
+pub use rule::{Direction, Monotonicity, RuleArgs, RuleBreak, SafetyRule, Violation};
+pub use stream::{evaluate_stream, StreamCounts, StreamedReport};
+
+/// The reports of an input, one per line, each with at least one level.
+///
+/// A report of a single level has no steps that could break a rule, so it is
+/// safe in both parts. A report of two levels is safe in part 1 when its one
+/// step follows the rule, and always safe in part 2 with a tolerance of at
+/// least one, as removing either level leaves a single one. Empty reports are
+/// rejected by [`parse_input`].
+#[derive(Debug)]
+pub struct InputStruct {
+    reports: Vec<Vec<i64>>,
+}
+
+/// day 2 options
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// levels part 2 may remove from a report to make it safe
+    pub tolerance: usize,
+    pub rule: SafetyRule,
+    /// threads the reports are split across, 1 evaluates them in order
+    pub threads: NonZeroUsize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            tolerance: 1,
+            rule: SafetyRule::default(),
+            threads: NonZeroUsize::MIN,
+        }
+    }
+}
+
+/// day 2 command line options
+#[derive(Args, Debug, Clone)]
+pub struct OptionArgs {
+    /// levels part 2 may remove from a report to make it safe
+    #[arg(long, default_value_t = 1)]
+    pub tolerance: usize,
+
+    #[command(flatten)]
+    pub rule: RuleArgs,
+
+    /// threads the reports are split across, 0 for one per core
+    #[arg(long, default_value_t = 1)]
+    pub threads: usize,
+}
+
+impl OptionArgs {
+    pub fn options(&self) -> Result<Options> {
+        Ok(Options {
+            tolerance: self.tolerance,
+            rule: self.rule.rule()?,
+            threads: NonZeroUsize::new(self.threads)
+                .unwrap_or_else(|| thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)),
+        })
+    }
+}
+
+/// error of an empty report before the last one
+const EMPTY_REPORT: &str = "expected levels, found an empty report";
+
+fn parse_input(input: &str) -> Result<InputStruct> {
+    let reports = parse_all(int_list(), input)?;
+    Ok(InputStruct {
+        reports: validate_reports(reports)?,
+    })
+}
+
+/// Reports of levels separated by blanks, one per line, a blank line being
+/// an empty report
+fn int_list<Input>() -> impl Parser<Input, Output = Vec<Vec<i64>>>
+where
+    Input: Stream<Token = char>,
+{
+    lines(report())
+}
+
+/// the levels of one line, empty for a blank line
+fn report<Input>() -> impl Parser<Input, Output = Vec<i64>>
+where
+    Input: Stream<Token = char>,
+{
+    optional(blanks())
+        .with(optional(row(integer())))
+        .map(Option::unwrap_or_default)
+}
+
+/// Drops the empty reports of trailing blank lines and fails on any other
+/// empty report, naming the lines of all of them.
+fn validate_reports(mut reports: Vec<Vec<i64>>) -> std::result::Result<Vec<Vec<i64>>, ParseError> {
+    while reports.last().is_some_and(Vec::is_empty) {
+        reports.pop();
+    }
+    if reports.is_empty() {
+        return Err(ParseError {
+            line: 1,
+            column: 1,
+            message: "expected at least one report".to_owned(),
+        });
+    }
+    let empty: Vec<usize> = (1..)
+        .zip(&reports)
+        .filter(|(_, report)| report.is_empty())
+        .map(|(line, _)| line)
+        .collect();
+    let message = match empty.as_slice() {
+        [] => return Ok(reports),
+        [_] => EMPTY_REPORT.to_owned(),
+        lines => {
+            let lines: Vec<_> = lines.iter().map(usize::to_string).collect();
+            format!(
+                "expected levels, found empty reports on lines {}",
+                lines.join(", ")
+            )
+        }
+    };
+    Err(ParseError {
+        line: i32::try_from(empty[0]).unwrap_or(i32::MAX),
+        column: 1,
+        message,
+    })
+}
+
+/// [`SafetyRule::min_removals`] of every report, in input order
+pub fn removals(input: &InputStruct, rule: &SafetyRule) -> Vec<usize> {
+    input
+        .reports
+        .iter()
+        .map(|report| rule.min_removals(report))
+        .collect()
+}
+
+/// Prints the fewest removals every report needs to be safe, and how many
+/// reports need each number of removals.
+pub fn print_removals(input: &InputStruct, rule: &SafetyRule) {
+    let removals = removals(input, rule);
+    for (line, removals) in removals.iter().enumerate() {
+        println!("line {}: {}", line + 1, removals);
+    }
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+    for removals in &removals {
+        *counts.entry(*removals).or_default() += 1;
+    }
+    println!("{:>8} {:>8}", "removals", "reports");
+    for (removals, reports) in counts {
+        println!("{:>8} {:>8}", removals, reports);
+    }
+}
+
+/// Number of reports `is_safe` holds for, with the reports split in
+/// contiguous chunks across `threads` scoped threads. Every report is counted
+/// once however they are split, so the count is the same as in order.
+fn count_safe(
+    reports: &[Vec<i64>],
+    threads: NonZeroUsize,
+    is_safe: impl Fn(&[i64]) -> bool + Sync,
+) -> usize {
+    let count = |reports: &[Vec<i64>]| reports.iter().filter(|report| is_safe(report)).count();
+    if threads.get() == 1 || reports.len() < 2 {
+        return count(reports);
+    }
+    let chunk_size = reports.len().div_ceil(threads.get());
+    thread::scope(|scope| {
+        let count = &count;
+        let counts: Vec<_> = reports
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || count(chunk)))
+            .collect();
+        counts
+            .into_iter()
+            .map(|count| count.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+            .sum()
+    })
+}
+
+fn pt1(input: &InputStruct, options: &Options) -> Result<usize> {
+    Ok(count_safe(&input.reports, options.threads, |report| {
+        options.rule.is_safe(report)
+    }))
+}
+
+fn pt2(input: &InputStruct, options: &Options) -> Result<usize> {
+    Ok(count_safe(&input.reports, options.threads, |report| {
+        options.rule.is_safe_within(report, options.tolerance)
+    }))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = InputStruct;
+    type Options = Options;
+
+    fn parse_input(input: &str) -> Result<InputStruct> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &InputStruct, options: &Options) -> Result<Answer> {
+        Ok(pt1(input, options)?.into())
+    }
+
+    fn pt2(input: &InputStruct, options: &Options) -> Result<Answer> {
+        Ok(pt2(input, options)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    /// the dampener as it was, trying the report without every level in turn
+    fn is_safe_dampened_brute_force(rule: &SafetyRule, report: &[i64]) -> bool {
+        (0..report.len()).any(|i| {
+            let mut new_report = report.to_vec();
+            new_report.remove(i);
+            rule.is_safe(&new_report)
+        })
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(pt1(&input, &Options::default()).unwrap(), 2);
+        assert_eq!(pt2(&input, &Options::default()).unwrap(), 4);
+    }
+
+    #[test]
+    fn removes_the_first_level() {
+        let rule = SafetyRule::default();
+        assert!(rule.is_safe_dampened(&[9, 1, 2, 3]));
+        assert!(rule.is_safe_dampened(&[1, 5, 4, 3]));
+    }
+
+    /// fewest removals found by trying every subset of levels to keep
+    fn min_removals_brute_force(rule: &SafetyRule, report: &[i64]) -> usize {
+        (0u32..1 << report.len())
+            .filter_map(|kept| {
+                let levels: Vec<_> = (0..report.len())
+                    .filter(|i| kept & 1 << i != 0)
+                    .map(|i| report[i])
+                    .collect();
+                rule.is_safe(&levels).then(|| report.len() - levels.len())
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn tolerates_removals() {
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(removals(&input, &SafetyRule::default()), [0, 2, 2, 1, 1, 0]);
+        let safe = |tolerance| {
+            let options = Options {
+                tolerance,
+                ..Options::default()
+            };
+            pt2(&input, &options).unwrap()
+        };
+        assert_eq!([safe(0), safe(1), safe(2)], [2, 4, 6]);
+    }
+
+    #[test]
+    fn rejects_empty_reports() {
+        let err = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!(
+            err("1 2\n\n3 4\n"),
+            "line 2, column 1: expected levels, found an empty report"
+        );
+        assert_eq!(
+            err("\n1 2\n"),
+            "line 1, column 1: expected levels, found an empty report"
+        );
+        assert_eq!(
+            err("1 2\n \n3\n\t\n4\n"),
+            "line 2, column 1: expected levels, found empty reports on lines 2, 4"
+        );
+        assert_eq!(err(""), "line 1, column 1: expected at least one report");
+        assert_eq!(
+            err(" \n\n"),
+            "line 1, column 1: expected at least one report"
+        );
+    }
+
+    #[test]
+    fn skips_trailing_blank_lines() {
+        let input = parse_input("1 2\r\n\r\n  \n").unwrap();
+        assert_eq!(input.reports, [vec![1, 2]]);
+    }
+
+    #[test]
+    fn single_levels_are_safe() {
+        let input = parse_input("5\n7\n").unwrap();
+        for tolerance in [0, 1, 2] {
+            let options = Options {
+                tolerance,
+                ..Options::default()
+            };
+            assert_eq!(pt1(&input, &options).unwrap(), 2);
+            assert_eq!(pt2(&input, &options).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn two_levels_are_safe_with_a_removal() {
+        let input = parse_input("1 5\n1 2\n3 3\n").unwrap();
+        let options = |tolerance| Options {
+            tolerance,
+            ..Options::default()
+        };
+        assert_eq!(pt1(&input, &options(1)).unwrap(), 1);
+        assert_eq!(pt2(&input, &options(0)).unwrap(), 1);
+        assert_eq!(pt2(&input, &options(1)).unwrap(), 3);
+    }
+
+    #[test]
+    fn applies_the_rule() {
+        let input = parse_input(EXAMPLE).unwrap();
+        let safe = |rule| {
+            let options = Options {
+                tolerance: 0,
+                rule,
+                ..Options::default()
+            };
+            pt1(&input, &options).unwrap()
+        };
+        let increasing = SafetyRule {
+            direction: Direction::Increasing,
+            ..SafetyRule::default()
+        };
+        assert_eq!(safe(increasing), 1);
+        let wide = SafetyRule {
+            max_step: 4,
+            ..SafetyRule::default()
+        };
+        assert_eq!(safe(wide), 3);
+        let non_strict = SafetyRule {
+            monotonicity: Monotonicity::NonStrict,
+            ..SafetyRule::default()
+        };
+        assert_eq!(safe(non_strict), 3);
+    }
+
+    #[test]
+    fn reads_rules_files() {
+        let rule: SafetyRule =
+            toml::from_str("max_step = 5\nmonotonicity = \"non-strict\"\n").unwrap();
+        assert_eq!(rule.max_step, 5);
+        assert_eq!(rule.monotonicity, Monotonicity::NonStrict);
+        assert_eq!(rule.min_step, 1);
+        assert!(toml::from_str::<SafetyRule>("max_stp = 5").is_err());
+    }
+
+    fn rules() -> impl Strategy<Value = SafetyRule> {
+        (1i64..3, 0i64..3, any::<bool>(), 0usize..3).prop_map(
+            |(min_step, extra, strict, direction)| SafetyRule {
+                min_step,
+                max_step: min_step + extra,
+                monotonicity: if strict {
+                    Monotonicity::Strict
+                } else {
+                    Monotonicity::NonStrict
+                },
+                direction: [
+                    Direction::Either,
+                    Direction::Increasing,
+                    Direction::Decreasing,
+                ][direction],
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn dampener_matches_brute_force(
+            rule in rules(),
+            report in prop::collection::vec(0i64..12, 1..10),
+        ) {
+            prop_assert_eq!(
+                rule.is_safe_dampened(&report),
+                is_safe_dampened_brute_force(&rule, &report)
+            );
+        }
+
+        #[test]
+        fn min_removals_matches_brute_force(
+            rule in rules(),
+            report in prop::collection::vec(0i64..12, 1..10),
+        ) {
+            prop_assert_eq!(rule.min_removals(&report), min_removals_brute_force(&rule, &report));
+        }
+
+        #[test]
+        fn violations_match_safety(
+            rule in rules(),
+            report in prop::collection::vec(0i64..12, 1..10),
+        ) {
+            prop_assert_eq!(rule.first_violation(&report).is_none(), rule.is_safe(&report));
+            let mut kept = report.clone();
+            for i in rule.removed_levels(&report).into_iter().rev() {
+                kept.remove(i);
+            }
+            prop_assert!(rule.is_safe(&kept));
+        }
+
+        #[test]
+        fn splits_reports_across_threads(
+            reports in prop::collection::vec(prop::collection::vec(0i64..10, 1..8), 0..120),
+            threads in 2usize..200,
+            tolerance in 0usize..3,
+        ) {
+            let input = InputStruct { reports };
+            let answers = |threads| {
+                let options = Options {
+                    tolerance,
+                    threads: NonZeroUsize::new(threads).unwrap(),
+                    ..Options::default()
+                };
+                (
+                    pt1(&input, &options).unwrap(),
+                    pt2(&input, &options).unwrap(),
+                )
+            };
+            prop_assert_eq!(answers(threads), answers(1));
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use aoc_common::Result;
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// whether adjacent levels may be equal
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Monotonicity {
+    /// every step changes the level
+    #[default]
+    Strict,
+    /// levels may also stay the same
+    NonStrict,
+}
+
+/// which way the levels of a safe report may go
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    /// all increasing or all decreasing
+    #[default]
+    Either,
+    Increasing,
+    Decreasing,
+}
+
+/// which part of a [`SafetyRule`] a step breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleBreak {
+    /// goes the other way than the steps before it
+    DirectionFlip,
+    /// goes the way the rule's direction does not allow
+    WrongDirection,
+    /// stays at the same level under a strict rule
+    ZeroStep,
+    StepTooSmall,
+    StepTooLarge,
+}
+
+impl fmt::Display for RuleBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            RuleBreak::DirectionFlip => "direction flip",
+            RuleBreak::WrongDirection => "wrong direction",
+            RuleBreak::ZeroStep => "zero step",
+            RuleBreak::StepTooSmall => "step too small",
+            RuleBreak::StepTooLarge => "step too large",
+        })
+    }
+}
+
+/// the first step of a report that breaks a [`SafetyRule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    /// 0-based index of the level the step goes to
+    pub index: usize,
+    pub rule: RuleBreak,
+}
+
+/// When a report is safe, read from a TOML file like the puzzle's rule:
+///
+/// ```toml
+/// min_step = 1
+/// max_step = 3
+/// monotonicity = "strict"
+/// direction = "either"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyRule {
+    /// smallest change between adjacent levels, apart from the equal levels
+    /// a non-strict rule allows
+    pub min_step: i64,
+    /// largest change between adjacent levels
+    pub max_step: i64,
+    pub monotonicity: Monotonicity,
+    pub direction: Direction,
+}
+
+impl Default for SafetyRule {
+    fn default() -> Self {
+        SafetyRule {
+            min_step: 1,
+            max_step: 3,
+            monotonicity: Monotonicity::Strict,
+            direction: Direction::Either,
+        }
+    }
+}
+
+impl SafetyRule {
+    pub fn load(path: &Path) -> Result<SafetyRule> {
+        let rule_str = read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        let rule: SafetyRule = toml::from_str(&rule_str)
+            .map_err(|err| format!("{}: {}", path.display(), err.message()))?;
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.min_step < 1 || self.min_step > self.max_step {
+            return Err(format!(
+                "expected 1 <= min_step <= max_step, got min_step {} and max_step {}",
+                self.min_step, self.max_step
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// the directions a safe report may go in, 1 for increasing and -1 for
+    /// decreasing
+    fn directions(&self) -> &'static [i64] {
+        match self.direction {
+            Direction::Either => &[1, -1],
+            Direction::Increasing => &[1],
+            Direction::Decreasing => &[-1],
+        }
+    }
+
+    /// which part of the rule going from level `a` to `b` in `direction`
+    /// breaks, `None` if it is an allowed step
+    fn step_break(&self, a: i64, b: i64, direction: i64) -> Option<RuleBreak> {
+        // levels far apart still have a difference in an i128
+        let diff = (i128::from(b) - i128::from(a)) * i128::from(direction);
+        match diff {
+            0 => (self.monotonicity == Monotonicity::Strict).then_some(RuleBreak::ZeroStep),
+            diff if diff < 0 => Some(RuleBreak::WrongDirection),
+            diff if diff < i128::from(self.min_step) => Some(RuleBreak::StepTooSmall),
+            diff if diff > i128::from(self.max_step) => Some(RuleBreak::StepTooLarge),
+            _ => None,
+        }
+    }
+
+    /// whether going from level `a` to `b` is an allowed step in `direction`
+    fn step_ok(&self, a: i64, b: i64, direction: i64) -> bool {
+        self.step_break(a, b, direction).is_none()
+    }
+
+    /// whether every step of the report is allowed in `direction`, leaving
+    /// out the level at index `skip`
+    fn steps_ok(&self, report: &[i64], direction: i64, skip: Option<usize>) -> bool {
+        let mut levels = report
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != skip)
+            .map(|(_, &level)| level);
+        let Some(mut previous) = levels.next() else {
+            return true;
+        };
+        levels.all(|level| {
+            let ok = self.step_ok(previous, level, direction);
+            previous = level;
+            ok
+        })
+    }
+
+    pub fn is_safe(&self, report: &[i64]) -> bool {
+        self.directions()
+            .iter()
+            .any(|&direction| self.steps_ok(report, direction, None))
+    }
+
+    /// Whether the report is safe with at most one level removed, in O(n).
+    ///
+    /// A level removed to fix the first bad step has to be one of its two
+    /// levels, otherwise that step is still there, so only those two
+    /// removals are checked per direction.
+    pub fn is_safe_dampened(&self, report: &[i64]) -> bool {
+        self.directions().iter().any(|&direction| {
+            let first_bad =
+                (1..report.len()).find(|&i| !self.step_ok(report[i - 1], report[i], direction));
+            match first_bad {
+                None => true,
+                Some(i) => {
+                    self.steps_ok(report, direction, Some(i - 1))
+                        || self.steps_ok(report, direction, Some(i))
+                }
+            }
+        })
+    }
+
+    /// The first step of the report that breaks the rule, `None` if the
+    /// report is safe.
+    ///
+    /// Under [`Direction::Either`] the first step that changes the level
+    /// decides the direction of the report.
+    pub fn first_violation(&self, report: &[i64]) -> Option<Violation> {
+        let mut direction = match self.direction {
+            Direction::Either => None,
+            Direction::Increasing => Some(1),
+            Direction::Decreasing => Some(-1),
+        };
+        (1..report.len()).find_map(|index| {
+            let (a, b) = (report[index - 1], report[index]);
+            let step_direction = direction.unwrap_or(if b < a { -1 } else { 1 });
+            let rule =
+                self.step_break(a, b, step_direction)
+                    .map(|rule| match (rule, self.direction) {
+                        (RuleBreak::WrongDirection, Direction::Either) => RuleBreak::DirectionFlip,
+                        (rule, _) => rule,
+                    });
+            if a != b {
+                direction.get_or_insert(step_direction);
+            }
+            rule.map(|rule| Violation { index, rule })
+        })
+    }
+
+    /// 0-based indices of a longest safe subsequence of levels going in
+    /// `direction`, in O(n²)
+    fn longest_safe(&self, report: &[i64], direction: i64) -> Vec<usize> {
+        // longest[i] is the longest safe subsequence ending in level i, and
+        // previous[i] the level before i in it, the latest of equally long
+        // ones so the earlier levels are removed like in the puzzle
+        let mut longest: Vec<usize> = Vec::with_capacity(report.len());
+        let mut previous: Vec<Option<usize>> = Vec::with_capacity(report.len());
+        for (i, &level) in report.iter().enumerate() {
+            let before = (0..i)
+                .filter(|&j| self.step_ok(report[j], level, direction))
+                .max_by_key(|&j| longest[j]);
+            longest.push(before.map_or(0, |j| longest[j]) + 1);
+            previous.push(before);
+        }
+        let mut kept = vec![];
+        let mut level = (0..report.len()).max_by_key(|&i| longest[i]);
+        while let Some(i) = level {
+            kept.push(i);
+            level = previous[i];
+        }
+        kept.reverse();
+        kept
+    }
+
+    /// 0-based indices of the fewest levels to remove to make the report
+    /// safe, from the longest safe subsequence of levels, in O(n²).
+    pub fn removed_levels(&self, report: &[i64]) -> Vec<usize> {
+        let kept = self
+            .directions()
+            .iter()
+            .map(|&direction| self.longest_safe(report, direction))
+            .reduce(|kept, other| {
+                if other.len() > kept.len() {
+                    other
+                } else {
+                    kept
+                }
+            })
+            .unwrap_or_default();
+        let mut kept = kept.into_iter().peekable();
+        (0..report.len())
+            .filter(|&i| kept.next_if_eq(&i).is_none())
+            .collect()
+    }
+
+    /// number of [`removed_levels`](Self::removed_levels)
+    pub fn min_removals(&self, report: &[i64]) -> usize {
+        self.removed_levels(report).len()
+    }
+
+    /// whether the report is safe with at most `tolerance` levels removed
+    pub fn is_safe_within(&self, report: &[i64], tolerance: usize) -> bool {
+        match tolerance {
+            0 => self.is_safe(report),
+            1 => self.is_safe_dampened(report),
+            _ => self.min_removals(report) <= tolerance,
+        }
+    }
+}
+
+/// Command line options for the [`SafetyRule`], each overriding the rules
+/// file, which overrides the puzzle's rule.
+#[derive(Args, Debug, Clone, Default)]
+pub struct RuleArgs {
+    /// TOML file with the safety rule
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
+
+    /// smallest change between adjacent levels [default: 1]
+    #[arg(long)]
+    pub min_step: Option<i64>,
+
+    /// largest change between adjacent levels [default: 3]
+    #[arg(long)]
+    pub max_step: Option<i64>,
+
+    /// whether adjacent levels may be equal [default: strict]
+    #[arg(long, value_enum)]
+    pub monotonicity: Option<Monotonicity>,
+
+    /// which way the levels may go [default: either]
+    #[arg(long, value_enum)]
+    pub direction: Option<Direction>,
+}
+
+impl RuleArgs {
+    pub fn rule(&self) -> Result<SafetyRule> {
+        let mut rule = match &self.rules {
+            Some(path) => SafetyRule::load(path)?,
+            None => SafetyRule::default(),
+        };
+        rule.min_step = self.min_step.unwrap_or(rule.min_step);
+        rule.max_step = self.max_step.unwrap_or(rule.max_step);
+        rule.monotonicity = self.monotonicity.unwrap_or(rule.monotonicity);
+        rule.direction = self.direction.unwrap_or(rule.direction);
+        rule.validate()?;
+        Ok(rule)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{report, Options, EMPTY_REPORT};
+use aoc_common::parse::{LineReader, ParseError};
+use aoc_common::{Error, Result};
+use std::io::{self, BufRead};
+
+/// reports evaluated so far, and how many of them are safe in either part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamCounts {
+    pub reports: usize,
+    pub safe: usize,
+    pub safe_pt2: usize,
+}
+
+/// one report of a stream as it was classified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamedReport {
+    /// 1-based input line
+    pub line: usize,
+    pub safe: bool,
+    pub safe_pt2: bool,
+    /// the counts including this report
+    pub counts: StreamCounts,
+}
+
+/// Reads reports line by line and classifies each as it arrives, passing it
+/// with the running counts to `on_report`, so inputs of any length are
+/// evaluated in constant memory.
+///
+/// Accepts the same inputs as [`parse_input`](crate::parse_input): blank
+/// lines are only allowed at the end, which a stream only knows once another
+/// report arrives, so an empty report is reported then. Returns the final
+/// counts, or the first error of reading, parsing or `on_report`.
+pub fn evaluate_stream(
+    reader: impl BufRead,
+    options: &Options,
+    mut on_report: impl FnMut(&StreamedReport) -> io::Result<()>,
+) -> Result<StreamCounts> {
+    let mut counts = StreamCounts::default();
+    let mut lines = LineReader::new(reader);
+    while let Some(line) = lines.next_line()? {
+        if let Some(blank_line) = line.after_blank {
+            let err = ParseError {
+                line: i32::try_from(blank_line).unwrap_or(i32::MAX),
+                column: 1,
+                message: EMPTY_REPORT.to_owned(),
+            };
+            return Err(Error::from(err).with_source_line(""));
+        }
+        let levels = line.parse(report())?;
+
+        let safe = options.rule.is_safe(&levels);
+        let safe_pt2 = options.rule.is_safe_within(&levels, options.tolerance);
+        counts.reports += 1;
+        counts.safe += usize::from(safe);
+        counts.safe_pt2 += usize::from(safe_pt2);
+        on_report(&StreamedReport {
+            line: line.number,
+            safe,
+            safe_pt2,
+            counts,
+        })?;
+    }
+    if counts.reports == 0 {
+        return Err(ParseError {
+            line: 1,
+            column: 1,
+            message: "expected at least one report".to_owned(),
+        }
+        .into());
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    fn evaluate(input: &str) -> Result<Vec<StreamedReport>> {
+        let mut reports = vec![];
+        evaluate_stream(input.as_bytes(), &Options::default(), |report| {
+            reports.push(*report);
+            Ok(())
+        })?;
+        Ok(reports)
+    }
+
+    #[test]
+    fn counts_the_example_as_it_goes() {
+        let reports = evaluate(EXAMPLE).unwrap();
+        let safe: Vec<_> = reports.iter().map(|report| report.counts.safe).collect();
+        assert_eq!(safe, [1, 1, 1, 1, 1, 2]);
+        let last = reports.last().unwrap();
+        assert_eq!(
+            last.counts,
+            StreamCounts {
+                reports: 6,
+                safe: 2,
+                safe_pt2: 4
+            }
+        );
+    }
+
+    #[test]
+    fn accepts_what_parse_input_accepts() {
+        let reports = evaluate("\u{feff}1 2\r\n3 3\r\n\r\n  \n").unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].line, 2);
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        let err = |input: &str| evaluate(input).unwrap_err().to_string();
+        assert_eq!(
+            err("1 2\n\n\n3 4\n"),
+            "line 2, column 1: expected levels, found an empty report"
+        );
+        assert_eq!(
+            err("1 2\n3 x\n"),
+            "line 2, column 3: unexpected `x`, expected integer"
+        );
+        assert_eq!(err(""), "line 1, column 1: expected at least one report");
+    }
+}
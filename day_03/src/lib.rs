@@ -0,0 +1,179 @@
+use aoc_common::parse::ParseError;
+use aoc_common::{Accumulator, Answer, Result, Solution};
+use clap::Args;
+use regex::{Match, Regex};
+
+/// an instruction found in the corrupted memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    /// `do()`, enables the `mul`s after it
+    Do,
+    /// `don't()`, disables the `mul`s after it
+    Dont,
+}
+
+/// an instruction with the byte offset it starts at in the memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub offset: usize,
+    pub instruction: Instruction,
+}
+
+/// day 3 options
+#[derive(Args, Debug, Clone, Default)]
+pub struct Options {
+    /// integer type the products are totalled in
+    #[arg(long, value_enum, default_value_t)]
+    pub accumulator: Accumulator,
+}
+
+#[derive(Debug)]
+pub struct InputStruct {
+    program: Vec<Token>,
+}
+
+/// a number matched in the memory, failing where it does not fit in an i64
+fn number(memory: &str, digits: Match) -> Result<i64> {
+    digits.as_str().parse().map_err(|_| {
+        ParseError::at_offset(memory, digits.start(), "number does not fit in i64").into()
+    })
+}
+
+/// `total + a * b`, failing instead of overflowing `accumulator`
+fn add_product(total: i128, (a, b): (i64, i64), accumulator: Accumulator) -> Result<i128> {
+    // the product of two i64s always fits in an i128
+    total
+        .checked_add(i128::from(a) * i128::from(b))
+        .and_then(|total| accumulator.fit(total))
+        .ok_or_else(|| format!("total overflows {} adding mul({},{})", accumulator, a, b).into())
+}
+
+/// The instructions of the corrupted memory in order, skipping everything
+/// else.
+pub fn tokenize(memory: &str) -> Result<Vec<Token>> {
+    let re = Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").unwrap();
+    re.captures_iter(memory)
+        .map(|cap| {
+            let instruction = match (cap.get(1), cap.get(2)) {
+                (Some(a), Some(b)) => Instruction::Mul(number(memory, a)?, number(memory, b)?),
+                _ if &cap[0] == "do()" => Instruction::Do,
+                _ => Instruction::Dont,
+            };
+            Ok(Token {
+                offset: cap.get(0).unwrap().start(),
+                instruction,
+            })
+        })
+        .collect()
+}
+
+fn parse_input(input_str: &str) -> Result<InputStruct> {
+    Ok(InputStruct {
+        program: tokenize(input_str)?,
+    })
+}
+
+/// Sum of the products of the program's `mul`s, of only the enabled ones
+/// when `conditional`.
+fn run(program: &[Token], conditional: bool, accumulator: Accumulator) -> Result<i128> {
+    let mut enabled = true;
+    let mut total = 0;
+    for token in program {
+        match token.instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont if conditional => enabled = false,
+            Instruction::Dont => (),
+            Instruction::Mul(a, b) if enabled => total = add_product(total, (a, b), accumulator)?,
+            Instruction::Mul(..) => (),
+        }
+    }
+    Ok(total)
+}
+
+fn pt1(input: &InputStruct, options: &Options) -> Result<i128> {
+    run(&input.program, false, options.accumulator)
+}
+
+fn pt2(input: &InputStruct, options: &Options) -> Result<i128> {
+    run(&input.program, true, options.accumulator)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = InputStruct;
+    type Options = Options;
+
+    fn parse_input(input: &str) -> Result<InputStruct> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &InputStruct, options: &Options) -> Result<Answer> {
+        Ok(pt1(input, options)?.into())
+    }
+
+    fn pt2(input: &InputStruct, options: &Options) -> Result<Answer> {
+        Ok(pt2(input, options)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn tokenizes_the_example() {
+        let tokens: Vec<_> = tokenize(EXAMPLE)
+            .unwrap()
+            .into_iter()
+            .map(|token| (token.offset, token.instruction))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (1, Instruction::Mul(2, 4)),
+                (20, Instruction::Dont),
+                (28, Instruction::Mul(5, 5)),
+                (48, Instruction::Mul(11, 8)),
+                (59, Instruction::Do),
+                (64, Instruction::Mul(8, 5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn solves_the_example() {
+        let input = parse_input(EXAMPLE).unwrap();
+        assert_eq!(pt1(&input, &Options::default()).unwrap(), 161);
+        assert_eq!(pt2(&input, &Options::default()).unwrap(), 48);
+    }
+
+    #[test]
+    fn totals_beyond_i64_in_i128() {
+        let max = i64::MAX;
+        let input = parse_input(&format!("mul({0},1)don't()mul({0},2)", max)).unwrap();
+        let err = pt1(&input, &Options::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("total overflows i64 adding mul({},2)", max)
+        );
+        let wide = Options {
+            accumulator: Accumulator::I128,
+        };
+        assert_eq!(pt1(&input, &wide).unwrap(), 3 * i128::from(max));
+        assert_eq!(pt2(&input, &Options::default()).unwrap(), i128::from(max));
+    }
+
+    #[test]
+    fn rejects_numbers_beyond_i64() {
+        let err = tokenize("xmul(99999999999999999999,2)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: number does not fit in i64"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use aoc_common::parse::{char_grid, parse_all};
+use aoc_common::{Answer, Result, Solution};
+use combine::Parser;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct InputStruct {
+    char_field: Vec<Vec<char>>,
+}
+
+fn parse_input(input_str: &str) -> Result<InputStruct> {
+    Ok(parse_all(
+        char_grid().map(|char_field| InputStruct { char_field }),
+        input_str,
+    )?)
+}
+
+fn isxmas(char1: char, char2: char, char3: char, char4: char) -> i32 {
+    if char1.eq_ignore_ascii_case(&'x')
+        && char2.eq_ignore_ascii_case(&'m')
+        && char3.eq_ignore_ascii_case(&'a')
+        && char4.eq_ignore_ascii_case(&'s')
+    {
+        1
+    } else {
+        0
+    }
+}
+
+fn pt1(input: &InputStruct) -> Result<i32> {
+    let mut xmasses = 0;
+    let y_len = input.char_field.len();
+    let x_len = input.char_field[0].len();
+
+    for y in 0..y_len {
+        for x in 0..x_len {
+            //horizontal forward
+            if x + 3 < x_len {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y][x + 1],
+                    input.char_field[y][x + 2],
+                    input.char_field[y][x + 3],
+                );
+            }
+            //horizontal backward
+            if x >= 3 {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y][x - 1],
+                    input.char_field[y][x - 2],
+                    input.char_field[y][x - 3],
+                );
+            }
+            //vertical downward
+            if y + 3 < y_len {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y + 1][x],
+                    input.char_field[y + 2][x],
+                    input.char_field[y + 3][x],
+                );
+            }
+            //vertical upward
+            if y >= 3 {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y - 1][x],
+                    input.char_field[y - 2][x],
+                    input.char_field[y - 3][x],
+                );
+            }
+
+            //diagonal right down
+            if x + 3 < x_len && y + 3 < y_len {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y + 1][x + 1],
+                    input.char_field[y + 2][x + 2],
+                    input.char_field[y + 3][x + 3],
+                );
+            }
+
+            //diagonal right up
+            if x + 3 < x_len && y >= 3 {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y - 1][x + 1],
+                    input.char_field[y - 2][x + 2],
+                    input.char_field[y - 3][x + 3],
+                );
+            }
+
+            //diagonal left down
+            if x >= 3 && y + 3 < y_len {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y + 1][x - 1],
+                    input.char_field[y + 2][x - 2],
+                    input.char_field[y + 3][x - 3],
+                );
+            }
+
+            //diagonal left up
+            if x >= 3 && y >= 3 {
+                xmasses += isxmas(
+                    input.char_field[y][x],
+                    input.char_field[y - 1][x - 1],
+                    input.char_field[y - 2][x - 2],
+                    input.char_field[y - 3][x - 3],
+                );
+            }
+        }
+    }
+    Ok(xmasses)
+}
+
+fn is_mas(chars: (char, char, char)) -> bool {
+    chars.0 == 'M' && chars.1 == 'A' && chars.2 == 'S'
+}
+
+/// M..
+/// .A.
+/// ..S
+fn down_right_pattern(x: usize, y: usize, char_field: &[Vec<char>]) -> (char, char, char) {
+    (
+        char_field[y][x],
+        char_field[y + 1][x + 1],
+        char_field[y + 2][x + 2],
+    )
+}
+
+/// ..M
+/// .A.
+/// S..
+fn down_left_pattern(x: usize, y: usize, char_field: &[Vec<char>]) -> (char, char, char) {
+    (
+        char_field[y][x + 2],
+        char_field[y + 1][x + 1],
+        char_field[y + 2][x],
+    )
+}
+
+/// ..S
+/// .A.
+/// M..
+fn up_right_pattern(x: usize, y: usize, char_field: &[Vec<char>]) -> (char, char, char) {
+    (
+        char_field[y + 2][x],
+        char_field[y + 1][x + 1],
+        char_field[y][x + 2],
+    )
+}
+
+/// S..
+/// .A.
+/// ..M
+fn up_left_pattern(x: usize, y: usize, char_field: &[Vec<char>]) -> (char, char, char) {
+    (
+        char_field[y + 2][x + 2],
+        char_field[y + 1][x + 1],
+        char_field[y][x],
+    )
+}
+
+fn pt2(input: &InputStruct) -> Result<usize> {
+    let y_len = input.char_field.len();
+    let x_len = input.char_field[0].len();
+
+    let mut matches: HashSet<(usize, usize)> = HashSet::new();
+
+    for y in 0..y_len.saturating_sub(2) {
+        for x in 0..x_len.saturating_sub(2) {
+            // Retrieve character tuples for each pattern orientation
+            let patterns = [
+                down_right_pattern,
+                down_left_pattern,
+                up_right_pattern,
+                up_left_pattern,
+            ]
+            .iter()
+            .combinations(2);
+
+            for pattern_pair in patterns {
+                if is_mas(pattern_pair[0](x, y, &input.char_field))
+                    && is_mas(pattern_pair[1](x, y, &input.char_field))
+                {
+                    matches.insert((x, y));
+                }
+            }
+        }
+    }
+
+    Ok(matches.len())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = InputStruct;
+    type Options = ();
+
+    fn parse_input(input: &str) -> Result<InputStruct> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &InputStruct, _options: &()) -> Result<Answer> {
+        Ok(pt1(input)?.into())
+    }
+
+    fn pt2(input: &InputStruct, _options: &()) -> Result<Answer> {
+        Ok(pt2(input)?.into())
+    }
+}
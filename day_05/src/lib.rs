@@ -0,0 +1,135 @@
+use aoc_common::parse::{blank_line, delimited, integer, lines, parse_all};
+use aoc_common::{Answer, Result, Solution};
+use combine::parser::char::char;
+use combine::Parser;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Input {
+    page_order_rules: Vec<(i64, i64)>,
+    update_pages: Vec<Vec<i64>>,
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let page_order_rule = (integer(), char('|'), integer()).map(|(head, _, tail)| (head, tail));
+    let pages = delimited(integer(), ',');
+    Ok(parse_all(
+        (lines(page_order_rule).skip(blank_line()), lines(pages)).map(
+            |(page_order_rules, update_pages)| Input {
+                page_order_rules,
+                update_pages,
+            },
+        ),
+        input,
+    )?)
+}
+
+fn valid_for_rule(update_page: Vec<i64>, page_order_rule: (i64, i64)) -> bool {
+    let mut found_tail = false;
+    let mut result = true;
+    for nr in update_page.iter() {
+        if *nr == page_order_rule.1 {
+            found_tail = true;
+        }
+        if *nr == page_order_rule.0 && found_tail {
+            result = false;
+        }
+    }
+    result
+}
+
+
+fn pt1(input: &Input) -> Result<i64> {
+    let mut lines_failing_rules = HashMap::new();
+    for line_nr in 0..input.update_pages.len(){
+        let mut fails = vec![];
+        for rule_nr in 0..input.page_order_rules.len() {
+            let mut found_second = false;
+            for nr in input.update_pages[line_nr].iter(){
+                if *nr == input.page_order_rules[rule_nr].1 {
+                    found_second = true;
+                }
+                if *nr == input.page_order_rules[rule_nr].0 && found_second {
+                    fails.push(rule_nr);
+                }
+            }
+        }
+        lines_failing_rules.insert(line_nr, fails);
+    }
+    //println!("{:#?}", lines_failing_rules);
+
+    let mut mids = vec![];
+    for (line_nr, fails) in lines_failing_rules.iter() {
+        if fails.is_empty() {
+        mids.push(input.update_pages[*line_nr][ input.update_pages[*line_nr].len() / 2]);
+        }
+    }
+
+    Ok(mids.iter().sum())
+}
+
+fn pt2(input: &Input) -> Result<i64> {
+    let mut lines_failing_rules = HashMap::new();
+    for line_nr in 0..input.update_pages.len() {
+        let mut fails = vec![];
+        for rule_nr in 0..input.page_order_rules.len() {
+            let mut found_second = false;
+            for nr in input.update_pages[line_nr].iter() {
+                if *nr == input.page_order_rules[rule_nr].1 {
+                    found_second = true;
+                }
+                if *nr == input.page_order_rules[rule_nr].0 && found_second {
+                    fails.push(rule_nr);
+                }
+            }
+        }
+        lines_failing_rules.insert(line_nr, fails);
+    }
+
+    let mut mids = vec![];
+    for (line_nr, fails) in lines_failing_rules.iter() {
+        if !fails.is_empty() {
+            let mut update_page = input.update_pages[*line_nr].clone();
+            let mut ordered = false;
+            while !ordered {
+                ordered = true;
+                for rule in input.page_order_rules.iter() {
+                    if !valid_for_rule(update_page.clone(), *rule) {
+                        ordered = false;
+                        let position = |page: i64| {
+                            update_page.iter().position(|&r| r == page).ok_or_else(|| {
+                                format!("page {} missing from update {}", page, line_nr + 1)
+                            })
+                        };
+                        let index_head = position(rule.0)?;
+                        let index_tail = position(rule.1)?;
+                        update_page.swap(index_head, index_tail);
+                    }
+                }
+            }
+            mids.push(update_page[update_page.len() / 2]);
+        }
+    }
+
+    Ok(mids.iter().sum())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Input;
+    type Options = ();
+
+    fn parse_input(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &Input, _options: &()) -> Result<Answer> {
+        Ok(pt1(input)?.into())
+    }
+
+    fn pt2(input: &Input, _options: &()) -> Result<Answer> {
+        Ok(pt2(input)?.into())
+    }
+}
@@ -0,0 +1,67 @@
+use aoc_common::parse::{char_grid, parse_all};
+use aoc_common::{Answer, Error, Result, Solution};
+use combine::Parser;
+
+#[derive(Debug)]
+pub struct Input {
+    grid: Vec<Vec<char>>,
+}
+
+fn parse_input(input_str: &str) -> Result<Input> {
+    Ok(parse_all(
+        char_grid().map(|grid| Input { grid }),
+        input_str,
+    )?)
+}
+
+/// work in progress, not wired into the parts yet
+#[allow(dead_code)]
+fn move_guard(input: &Input) -> Option<(usize,usize)> {
+    let cur_pos = input
+        .grid
+        .iter()
+        .enumerate()
+        .find_map(|(row_id, row)| {
+            row.iter()
+                .enumerate()
+                .find_map(|(col_id, &c)| if ['^', '>', '<', 'v'].contains(&c) { Some((row_id, col_id, c)) } else { None })
+        });
+
+    match cur_pos?.2 {
+        '^' => Some((cur_pos?.0 - 1, cur_pos?.1)),
+        'v' => Some((cur_pos?.0 + 1, cur_pos?.1)),
+        '<' => Some((cur_pos?.0,     cur_pos?.1 - 1)),
+        '>' => Some((cur_pos?.0,     cur_pos?.1 + 1)),
+        _ => None
+    }
+}
+
+fn pt1(_input: &Input) -> Result<i64> {
+
+
+    Err(Error::not_implemented())
+}
+
+fn pt2(_input: &Input) -> Result<i64> {
+    Err(Error::not_implemented())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Input;
+    type Options = ();
+
+    fn parse_input(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+
+    fn pt1(input: &Input, _options: &()) -> Result<Answer> {
+        Ok(pt1(input)?.into())
+    }
+
+    fn pt2(input: &Input, _options: &()) -> Result<Answer> {
+        Ok(pt2(input)?.into())
+    }
+}